@@ -0,0 +1,238 @@
+use crate::enemy::enemy_movement::MovementPattern;
+use crate::{AssetHolder, GameState};
+use bevy::asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy::prelude::*;
+use bevy::reflect::TypeUuid;
+use bevy::utils::BoxedFuture;
+use iyes_loopless::prelude::*;
+use rand::distributions::WeightedIndex;
+use rand::prelude::*;
+use serde::Deserialize;
+
+pub(crate) struct EnemyArchetypePlugin;
+
+impl Plugin for EnemyArchetypePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<EnemyArchetypeList>()
+            .init_asset_loader::<EnemyArchetypeLoader>()
+            .init_resource::<EnemyArchetypes>()
+            .add_enter_system(GameState::GameSetupOnce, setup_enemy_archetypes);
+
+        // picks up edits to the archetype file while the game is running (watch_for_changes)
+        app.add_system(
+            reload_enemy_archetypes
+                .run_not_in_state(GameState::AssetLoading)
+                .run_on_event::<AssetEvent<EnemyArchetypeList>>(),
+        );
+    }
+}
+
+/// The raw archetype file as it is written in assets/data/*.archetypes.ron
+#[derive(Deserialize, TypeUuid)]
+#[uuid = "5d4d3c7e-2b4f-4a63-9d0e-8f1c6e0b7a21"]
+pub(crate) struct EnemyArchetypeList {
+    pub(crate) archetypes: Vec<EnemyArchetype>,
+    pub(crate) spawn_tables: Vec<EnemySpawnTable>,
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(default)]
+pub(crate) struct EnemyArchetype {
+    pub(crate) name: String,
+    pub(crate) sprite: String,
+    pub(crate) scale: f32,
+    pub(crate) speed_multiplier: f32,
+    pub(crate) hit_points: f32,
+    pub(crate) collider_radius: f32,
+    pub(crate) score_value: u32,
+    // how many of this archetype spawn together each time it is picked
+    pub(crate) group_size: u32,
+    pub(crate) movement: MovementPattern,
+
+    #[serde(skip)]
+    pub(crate) texture: Handle<Image>,
+}
+
+impl Default for EnemyArchetype {
+    fn default() -> Self {
+        EnemyArchetype {
+            name: "grunt".to_string(),
+            sprite: "enemy.png".to_string(),
+            scale: 3.0,
+            speed_multiplier: 1.0,
+            hit_points: 1.0,
+            collider_radius: 5.0,
+            score_value: 5,
+            group_size: 1,
+            movement: MovementPattern::Straight,
+
+            texture: Default::default(),
+        }
+    }
+}
+
+/// A weighted table of archetypes that is used once the difficulty level reaches min_difficulty
+#[derive(Deserialize, Clone)]
+pub(crate) struct EnemySpawnTable {
+    pub(crate) min_difficulty: u32,
+    pub(crate) weights: Vec<(String, u32)>,
+}
+
+#[derive(Default)]
+pub(crate) struct EnemyArchetypeLoader;
+
+impl AssetLoader for EnemyArchetypeLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let archetype_list = ron::de::from_bytes::<EnemyArchetypeList>(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(archetype_list));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["archetypes.ron"]
+    }
+}
+
+/// The registry the spawner picks from. Built from the loaded EnemyArchetypeList with the sprites resolved
+#[derive(Default)]
+pub(crate) struct EnemyArchetypes {
+    pub(crate) archetypes: Vec<EnemyArchetype>,
+    pub(crate) spawn_tables: Vec<EnemySpawnTable>,
+}
+
+impl EnemyArchetypes {
+    fn load_from(&mut self, archetype_list: &EnemyArchetypeList, asset_server: &Res<AssetServer>) {
+        self.archetypes = archetype_list.archetypes.clone();
+        for archetype in self.archetypes.iter_mut() {
+            archetype.texture = asset_server.load(archetype.sprite.as_str());
+        }
+        self.spawn_tables = archetype_list.spawn_tables.clone();
+        info!("loaded {} enemy archetypes", self.archetypes.len());
+    }
+
+    pub(crate) fn get(&self, name: &str) -> Option<&EnemyArchetype> {
+        self.archetypes
+            .iter()
+            .find(|archetype| archetype.name == name)
+    }
+
+    /// Picks an archetype from the highest spawn table unlocked at this difficulty level.
+    /// Falls back to the first archetype, or the default one if the file had none
+    pub(crate) fn pick_archetype(&self, difficulty_level: u32, rng: &mut impl Rng) -> EnemyArchetype {
+        let spawn_table = self
+            .spawn_tables
+            .iter()
+            .filter(|table| table.min_difficulty <= difficulty_level)
+            .max_by_key(|table| table.min_difficulty);
+
+        if let Some(spawn_table) = spawn_table {
+            let candidates: Vec<(&EnemyArchetype, u32)> = spawn_table
+                .weights
+                .iter()
+                .filter_map(|(name, weight)| self.get(name).map(|archetype| (archetype, *weight)))
+                .collect();
+            if let Ok(distribution) = WeightedIndex::new(candidates.iter().map(|(_, weight)| *weight)) {
+                return candidates[distribution.sample(rng)].0.clone();
+            }
+        }
+
+        self.archetypes.first().cloned().unwrap_or_default()
+    }
+}
+
+fn setup_enemy_archetypes(
+    sprites: Res<AssetHolder>,
+    archetype_lists: Res<Assets<EnemyArchetypeList>>,
+    asset_server: Res<AssetServer>,
+    mut enemy_archetypes: ResMut<EnemyArchetypes>,
+) {
+    if let Some(archetype_list) = archetype_lists.get(&sprites.enemy_archetypes) {
+        enemy_archetypes.load_from(archetype_list, &asset_server);
+    }
+}
+
+fn reload_enemy_archetypes(
+    mut asset_events: EventReader<AssetEvent<EnemyArchetypeList>>,
+    sprites: Res<AssetHolder>,
+    archetype_lists: Res<Assets<EnemyArchetypeList>>,
+    asset_server: Res<AssetServer>,
+    mut enemy_archetypes: ResMut<EnemyArchetypes>,
+) {
+    for event in asset_events.iter() {
+        if let AssetEvent::Modified { handle } = event {
+            if *handle == sprites.enemy_archetypes {
+                if let Some(archetype_list) = archetype_lists.get(handle) {
+                    enemy_archetypes.load_from(archetype_list, &asset_server);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn archetype(name: &str) -> EnemyArchetype {
+        EnemyArchetype {
+            name: name.to_string(),
+            ..default()
+        }
+    }
+
+    fn test_archetypes() -> EnemyArchetypes {
+        EnemyArchetypes {
+            archetypes: vec![archetype("grunt"), archetype("tank")],
+            spawn_tables: vec![
+                EnemySpawnTable {
+                    min_difficulty: 0,
+                    weights: vec![("grunt".to_string(), 1)],
+                },
+                EnemySpawnTable {
+                    min_difficulty: 5,
+                    weights: vec![("grunt".to_string(), 0), ("tank".to_string(), 1)],
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn pick_archetype_uses_the_highest_unlocked_table() {
+        let archetypes = test_archetypes();
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..20 {
+            assert_eq!(archetypes.pick_archetype(4, &mut rng).name, "grunt");
+            assert_eq!(archetypes.pick_archetype(5, &mut rng).name, "tank");
+        }
+    }
+
+    #[test]
+    fn pick_archetype_is_deterministic_for_a_seed() {
+        let mut archetypes = test_archetypes();
+        archetypes.spawn_tables[0].weights.push(("tank".to_string(), 3));
+        let picks = |seed| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            (0..10)
+                .map(|_| archetypes.pick_archetype(0, &mut rng).name)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(picks(42), picks(42));
+    }
+
+    #[test]
+    fn pick_archetype_falls_back_without_a_usable_table() {
+        let mut archetypes = test_archetypes();
+        archetypes.spawn_tables[0].weights = vec![("missing".to_string(), 1)];
+        let mut rng = StdRng::seed_from_u64(0);
+        assert_eq!(archetypes.pick_archetype(0, &mut rng).name, "grunt");
+
+        let empty = EnemyArchetypes::default();
+        assert_eq!(empty.pick_archetype(0, &mut rng).name, "grunt");
+    }
+}
@@ -0,0 +1,203 @@
+use crate::enemy::enemy_archetypes::{EnemyArchetype, EnemyArchetypes};
+use crate::enemy::enemy_difficulty::EnemyStats;
+use crate::enemy::enemy_movement::EnemyMovement;
+use crate::enemy::Enemy;
+use crate::game_systems::GameRng;
+use crate::player::planets::Planets;
+use crate::sound::SoundEffectEvents;
+use crate::{AssetHolder, GameState, RestartGameEvent, ScoreEvent};
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use iyes_loopless::prelude::*;
+use rand::prelude::*;
+use std::f32::consts::TAU;
+use std::time::Duration;
+
+pub(crate) struct EnemyBossPlugin;
+
+impl Plugin for EnemyBossPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<BossWaveEvent>();
+        app.add_system_set(
+            ConditionSet::new()
+                .with_system(handle_restart_game_events.run_on_event::<RestartGameEvent>())
+                .into(),
+        );
+        app.add_system_set(
+            ConditionSet::new()
+                .run_in_state(GameState::Playing)
+                .label("boss_loop")
+                .after("enemy_movement")
+                .before("main_enemy_loop")
+                .with_system(handle_boss_wave_events.run_on_event::<BossWaveEvent>())
+                .with_system(update_bosses)
+                .into(),
+        );
+    }
+}
+
+/// Sent by the difficulty curve every EnemyStats::boss_level_interval levels
+pub(crate) struct BossWaveEvent;
+
+/// The mothership hull. Always visible, only its weak points can be hurt
+#[derive(Component)]
+pub(crate) struct Boss {
+    orbit_radius: f32,
+    orbit_angle: f32,
+    orbit_speed: f32,
+    child_spawn_timer: Timer,
+    weak_points: Vec<Entity>,
+    kill_score: u32,
+}
+
+/// A part of the boss. Weak points are normal enemies that are hidden until scanned
+#[derive(Component)]
+pub(crate) struct BossWeakPoint {
+    offset: Vec2,
+}
+
+fn handle_boss_wave_events(
+    sprites: Res<AssetHolder>,
+    mut enemy_stats: ResMut<EnemyStats>,
+    mut game_rng: ResMut<GameRng>,
+    mut commands: Commands,
+    mut sound_effect_writer: EventWriter<SoundEffectEvents>,
+) {
+    if enemy_stats.is_boss_alive {
+        return;
+    }
+    enemy_stats.is_boss_alive = true;
+    enemy_stats.boss_count += 1;
+    sound_effect_writer.send(SoundEffectEvents::BossMusic(true));
+
+    let orbit_radius = 380.;
+    let orbit_angle = game_rng.rng.gen_range(0.0..TAU);
+    let boss_location = Vec2::new(orbit_angle.cos(), orbit_angle.sin()) * orbit_radius;
+
+    let weak_point_archetype = EnemyArchetype {
+        name: "boss_weak_point".to_string(),
+        hit_points: 2.0 + enemy_stats.boss_count as f32,
+        collider_radius: 6.0,
+        score_value: 25,
+        ..default()
+    };
+
+    let mut weak_points: Vec<Entity> = vec![];
+    for i in 0..3 {
+        let offset_angle = TAU / 3. * i as f32;
+        let offset = Vec2::new(offset_angle.cos(), offset_angle.sin()) * 40.;
+        let weak_point = Enemy::spawn(
+            &sprites,
+            &weak_point_archetype,
+            enemy_stats.speed,
+            &mut commands,
+            &(boss_location + offset),
+            // weak points don't fly at anything, the movement is taken off below
+            Vec2::ZERO,
+        );
+        // weak points ride along with the hull instead of flying at the planet
+        commands
+            .entity(weak_point)
+            .remove::<EnemyMovement>()
+            .insert(BossWeakPoint { offset });
+        weak_points.push(weak_point);
+    }
+
+    commands
+        .spawn_bundle(SpriteBundle {
+            sprite: Sprite {
+                color: Color::rgb(0.6, 0.2, 0.2),
+                ..default()
+            },
+            transform: Transform {
+                translation: boss_location.extend(5.0),
+                scale: Vec3 {
+                    x: 10.0,
+                    y: 10.0,
+                    z: 1.0,
+                },
+                ..default()
+            },
+            texture: sprites.enemy.clone(),
+            ..default()
+        })
+        .insert(Boss {
+            orbit_radius,
+            orbit_angle,
+            orbit_speed: 0.15,
+            child_spawn_timer: Timer::new(Duration::from_secs_f32(4.0), true),
+            weak_points,
+            kill_score: 100 * enemy_stats.boss_count,
+        });
+}
+
+fn update_bosses(
+    sprites: Res<AssetHolder>,
+    enemy_archetypes: Res<EnemyArchetypes>,
+    mut bosses: Query<(Entity, &mut Boss, &mut Transform)>,
+    mut weak_points: Query<(&BossWeakPoint, &Transform, &mut Velocity), Without<Boss>>,
+    planets: Res<Planets>,
+    mut enemy_stats: ResMut<EnemyStats>,
+    mut game_rng: ResMut<GameRng>,
+    mut commands: Commands,
+    mut score_event_writer: EventWriter<ScoreEvent>,
+    mut sound_effect_writer: EventWriter<SoundEffectEvents>,
+    time: Res<Time>,
+) {
+    for (entity, mut boss, mut transform) in bosses.iter_mut() {
+        boss.weak_points
+            .retain(|weak_point| weak_points.get(*weak_point).is_ok());
+        if boss.weak_points.is_empty() {
+            commands.entity(entity).despawn();
+            score_event_writer.send(ScoreEvent(boss.kill_score));
+            sound_effect_writer.send(SoundEffectEvents::BossMusic(false));
+            enemy_stats.is_boss_alive = false;
+            continue;
+        }
+
+        boss.orbit_angle += boss.orbit_speed * time.delta_seconds();
+        let boss_location = Vec2::new(boss.orbit_angle.cos(), boss.orbit_angle.sin()) * boss.orbit_radius;
+        let boss_velocity = Vec2::new(-boss.orbit_angle.sin(), boss.orbit_angle.cos())
+            * boss.orbit_speed
+            * boss.orbit_radius;
+        transform.translation = boss_location.extend(transform.translation.z);
+        transform.rotation = Quat::from_rotation_z(boss.orbit_angle);
+
+        for weak_point_entity in boss.weak_points.iter() {
+            if let Ok((weak_point, weak_point_transform, mut velocity)) =
+            weak_points.get_mut(*weak_point_entity)
+            {
+                let target = boss_location + transform.rotation.mul_vec3(weak_point.offset.extend(0.)).truncate();
+                let drift = target - weak_point_transform.translation.truncate();
+                velocity.linvel = boss_velocity + drift * 5.0;
+            }
+        }
+
+        // the boss launches normal enemies at the planet while it is alive
+        boss.child_spawn_timer.tick(time.delta());
+        if boss.child_spawn_timer.just_finished() {
+            let archetype = enemy_archetypes.pick_archetype(enemy_stats.difficulty_level, &mut game_rng.rng);
+            let target = planets.pick_target(&mut game_rng.rng);
+            Enemy::spawn(
+                &sprites,
+                &archetype,
+                enemy_stats.speed,
+                &mut commands,
+                &boss_location,
+                target,
+            );
+            enemy_stats.all_time_enemy_count += 1;
+        }
+    }
+}
+
+fn handle_restart_game_events(
+    mut commands: Commands,
+    bosses: Query<Entity, With<Boss>>,
+    mut sound_effect_writer: EventWriter<SoundEffectEvents>,
+) {
+    for boss in bosses.iter() {
+        commands.entity(boss).despawn();
+        sound_effect_writer.send(SoundEffectEvents::BossMusic(false));
+    }
+}
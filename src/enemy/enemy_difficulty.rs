@@ -1,4 +1,6 @@
-﻿use crate::enemy::enemy_spawner::EnemySpawnerPlugin;
+﻿use crate::enemy::enemy_boss::BossWaveEvent;
+use crate::enemy::enemy_spawner::EnemySpawnerPlugin;
+use crate::enemy::enemy_wave_script::is_wave_script_finished;
 use crate::enemy::Enemy;
 use crate::{GameState, PlayerStats, RestartGameEvent};
 use std::time::Duration;
@@ -29,7 +31,9 @@ impl Plugin for EnemyDifficultyPlugin {
         enemy_difficulty_fixed_update.add_system(
             update_enemy_difficulty
                 // only do it in-game
-                .run_in_state(GameState::Playing),
+                .run_in_state(GameState::Playing)
+                // the curve only starts once the wave script has run out
+                .run_if(is_wave_script_finished),
         );
         app.add_stage_before(
             CoreStage::Update,
@@ -57,6 +61,11 @@ pub(crate) struct EnemyStats {
     pub(crate) time_between_microwaves: f32,
     pub(crate) time_till_next_microwave: f32,
 
+    //boss stuff
+    pub(crate) boss_level_interval: u32,
+    pub(crate) is_boss_alive: bool,
+    pub(crate) boss_count: u32,
+
     //assorted stuff
     pub(crate) current_enemy_amount: u32,
 
@@ -80,6 +89,10 @@ impl Default for EnemyStats {
             time_between_microwaves: 15.0,
             time_till_next_microwave: 0.0,
 
+            boss_level_interval: 10,
+            is_boss_alive: false,
+            boss_count: 0,
+
             current_enemy_amount: 0,
             all_time_enemy_count: 0,
         }
@@ -106,6 +119,7 @@ fn update_enemy_difficulty(
     player_stats: Res<PlayerStats>,
     time: Res<Time>,
     mut timesteps: ResMut<FixedTimestepInfo>,
+    mut boss_wave_event_writer: EventWriter<BossWaveEvent>,
 ) {
     timesteps.step = Duration::from_secs_f32(enemy_stats.time_between_waves);
 
@@ -127,6 +141,10 @@ fn update_enemy_difficulty(
         }
         enemy_stats.upgrade_wave = false;
         enemy_stats.difficulty_level += 1;
+
+        if enemy_stats.difficulty_level % enemy_stats.boss_level_interval == 0 {
+            boss_wave_event_writer.send(BossWaveEvent);
+        }
     } else {
         enemy_stats.upgrade_wave = true;
     }
@@ -0,0 +1,137 @@
+use crate::enemy::{Enemy, Scanned};
+use crate::{AssetHolder, GameState, PlayerStats, RestartGameEvent};
+use bevy::prelude::*;
+use iyes_loopless::prelude::*;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+pub(crate) struct GhostTrailPlugin;
+
+impl Plugin for GhostTrailPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(
+            ConditionSet::new()
+                .run_in_state(GameState::Playing)
+                .label("ghost_trail")
+                // has to see the ghost before handle_enemy_scanned moves it to the new sighting
+                .before("main_enemy_loop")
+                .with_system(record_ghost_trails)
+                .with_system(fade_trail_ghosts)
+                .into(),
+        );
+        app.add_system_set(
+            ConditionSet::new()
+                .with_system(handle_restart_game_events.run_on_event::<RestartGameEvent>())
+                .into(),
+        );
+    }
+}
+
+// seconds a trail ghost takes to fade out completely
+const TRAIL_FADE_TIME: f32 = 8.0;
+// alpha of a brand new trail ghost
+const TRAIL_START_ALPHA: f32 = 0.5;
+
+/// Ring buffer of the trail ghosts left at an enemy's past sightings, oldest first
+#[derive(Component, Default)]
+pub(crate) struct GhostTrail {
+    sightings: VecDeque<Entity>,
+}
+
+/// One past sighting of an enemy
+#[derive(Component)]
+pub(crate) struct TrailGhost {
+    enemy: Entity,
+    fade_timer: Timer,
+}
+
+fn record_ghost_trails(
+    sprites: Res<AssetHolder>,
+    mut scanned_enemies: Query<(Entity, &Enemy, Option<&mut GhostTrail>), With<Scanned>>,
+    ghosts: Query<&Transform, Without<Enemy>>,
+    player_stats: Res<PlayerStats>,
+    mut commands: Commands,
+) {
+    let trail_length = player_stats.ghost_trail_length.0 as usize;
+    for (enemy_entity, enemy, ghost_trail) in scanned_enemies.iter_mut() {
+        // on the first scan the main ghost is still where the enemy spawned, which was never a sighting.
+        // The trail is started even without the upgrade so buying it later doesn't record that spot either
+        let mut ghost_trail = match ghost_trail {
+            Some(ghost_trail) => ghost_trail,
+            None => {
+                commands.entity(enemy_entity).insert(GhostTrail::default());
+                continue;
+            }
+        };
+        if trail_length == 0 {
+            continue;
+        }
+        // the main ghost still sits at the last sighting, which is about to become part of the trail
+        let last_sighting = match ghosts.get(enemy.scan_ghost) {
+            Ok(ghost_transform) => ghost_transform.translation,
+            Err(_) => continue,
+        };
+        let trail_ghost = commands
+            .spawn_bundle(SpriteBundle {
+                sprite: Sprite {
+                    color: Color::rgba(1., 1., 1., TRAIL_START_ALPHA),
+                    ..default()
+                },
+                transform: Transform {
+                    translation: last_sighting.truncate().extend(-0.1),
+                    scale: Vec3::new(3.0, 3.0, 1.0),
+                    ..default()
+                },
+                texture: sprites.enemy_ghost.clone(),
+                ..default()
+            })
+            .insert(TrailGhost {
+                enemy: enemy_entity,
+                fade_timer: Timer::new(Duration::from_secs_f32(TRAIL_FADE_TIME), false),
+            })
+            .id();
+
+        ghost_trail.sightings.push_back(trail_ghost);
+        while ghost_trail.sightings.len() > trail_length {
+            if let Some(oldest) = ghost_trail.sightings.pop_front() {
+                commands.entity(oldest).despawn();
+            }
+        }
+    }
+}
+
+fn fade_trail_ghosts(
+    mut trail_ghosts: Query<(Entity, &mut TrailGhost, &mut Sprite)>,
+    mut enemies: Query<&mut GhostTrail, With<Enemy>>,
+    mut commands: Commands,
+    time: Res<Time>,
+) {
+    for (entity, mut trail_ghost, mut sprite) in trail_ghosts.iter_mut() {
+        trail_ghost.fade_timer.tick(time.delta());
+        match enemies.get_mut(trail_ghost.enemy) {
+            // the enemy died or reached the planet, its trail goes with it
+            Err(_) => {
+                commands.entity(entity).despawn();
+            }
+            Ok(mut ghost_trail) => {
+                if trail_ghost.fade_timer.finished() {
+                    ghost_trail.sightings.retain(|sighting| *sighting != entity);
+                    commands.entity(entity).despawn();
+                } else {
+                    sprite
+                        .color
+                        .set_a(TRAIL_START_ALPHA * trail_ghost.fade_timer.percent_left());
+                }
+            }
+        }
+    }
+}
+
+fn handle_restart_game_events(
+    mut commands: Commands,
+    trail_ghosts: Query<Entity, With<TrailGhost>>,
+) {
+    for trail_ghost in trail_ghosts.iter() {
+        commands.entity(trail_ghost).despawn();
+    }
+}
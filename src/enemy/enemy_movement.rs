@@ -0,0 +1,133 @@
+use crate::enemy::Enemy;
+use crate::GameState;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use iyes_loopless::prelude::*;
+use serde::Deserialize;
+
+pub(crate) struct EnemyMovementPlugin;
+
+impl Plugin for EnemyMovementPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(
+            ConditionSet::new()
+                .run_in_state(GameState::Playing)
+                .label("enemy_movement")
+                .before("main_enemy_loop")
+                .with_system(update_enemy_movement)
+                .into(),
+        );
+    }
+}
+
+/// How an enemy flies toward its target. Chosen per archetype in the archetype file
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+pub(crate) enum MovementPattern {
+    Straight,
+    // weaves side to side across the straight line, never more than amplitude away from it
+    Sine { amplitude: f32, frequency: f32 },
+    // circles the target while closing in at the normal speed
+    Spiral { orbit_speed: f32 },
+    // starts at a quarter speed and speeds up to max_multiplier times the normal speed
+    Accelerating { acceleration: f32, max_multiplier: f32 },
+    // moves for move_time then sits still for stop_time, averaging out to the normal speed
+    StopAndGo { move_time: f32, stop_time: f32 },
+}
+
+impl Default for MovementPattern {
+    fn default() -> Self {
+        MovementPattern::Straight
+    }
+}
+
+#[derive(Component)]
+pub(crate) struct EnemyMovement {
+    pub(crate) pattern: MovementPattern,
+    pub(crate) speed: f32,
+    pub(crate) target: Vec2,
+    pub(crate) time_alive: f32,
+}
+
+impl EnemyMovement {
+    pub(crate) fn new(pattern: MovementPattern, speed: f32, target: Vec2) -> EnemyMovement {
+        EnemyMovement {
+            pattern,
+            speed,
+            target,
+            time_alive: 0.0,
+        }
+    }
+
+    pub(crate) fn velocity(&self, position: Vec2) -> Vec2 {
+        let heading = (self.target - position).normalize_or_zero();
+        let side = heading.perp();
+        match self.pattern {
+            MovementPattern::Straight => heading * self.speed,
+            MovementPattern::Sine {
+                amplitude,
+                frequency,
+            } => {
+                let weave = amplitude * frequency * (frequency * self.time_alive).cos();
+                heading * self.speed + side * weave
+            }
+            MovementPattern::Spiral { orbit_speed } => heading * self.speed + side * orbit_speed,
+            MovementPattern::Accelerating {
+                acceleration,
+                max_multiplier,
+            } => {
+                let multiplier = (0.25 + acceleration * self.time_alive).min(max_multiplier);
+                heading * self.speed * multiplier
+            }
+            MovementPattern::StopAndGo {
+                move_time,
+                stop_time,
+            } => {
+                let cycle_time = move_time + stop_time;
+                if move_time <= 0. {
+                    heading * self.speed
+                } else if self.time_alive % cycle_time < move_time {
+                    heading * self.speed * (cycle_time / move_time)
+                } else {
+                    Vec2::ZERO
+                }
+            }
+        }
+    }
+}
+
+/// Scales an enemy's speed down until the timer runs out. Added by the emp
+#[derive(Component)]
+pub(crate) struct Slowed {
+    pub(crate) multiplier: f32,
+    pub(crate) timer: Timer,
+}
+
+fn update_enemy_movement(
+    mut enemies: Query<
+        (
+            Entity,
+            &mut EnemyMovement,
+            &Transform,
+            &mut Velocity,
+            Option<&mut Slowed>,
+        ),
+        With<Enemy>,
+    >,
+    mut commands: Commands,
+    time: Res<Time>,
+) {
+    for (entity, mut movement, transform, mut velocity, slowed) in enemies.iter_mut() {
+        let mut speed_multiplier = 1.0;
+        if let Some(mut slowed) = slowed {
+            slowed.timer.tick(time.delta());
+            if slowed.timer.finished() {
+                commands.entity(entity).remove::<Slowed>();
+            } else {
+                speed_multiplier = slowed.multiplier;
+            }
+        }
+        // slowed enemies also run through their pattern slower so they don't snap ahead afterwards
+        movement.time_alive += time.delta_seconds() * speed_multiplier;
+        velocity.linvel = movement.velocity(transform.translation.truncate()) * speed_multiplier;
+    }
+}
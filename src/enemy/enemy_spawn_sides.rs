@@ -0,0 +1,46 @@
+use crate::enemy::enemy_spawner::SpawnSide;
+use bevy::asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy::reflect::TypeUuid;
+use bevy::utils::BoxedFuture;
+use serde::Deserialize;
+
+/// The base chance of each side being picked for a procedural spawn, as written in assets/data/*.spawns.ron
+#[derive(Deserialize, TypeUuid)]
+#[uuid = "c81f4e2a-93d7-4b5e-a6c0-7e2d9f1b3a58"]
+pub(crate) struct SpawnSideWeights {
+    pub(crate) weights: Vec<(SpawnSide, f32)>,
+}
+
+impl SpawnSideWeights {
+    /// The weights in SpawnSide::ALL order. Sides left out of the file never get picked
+    pub(crate) fn to_array(&self) -> [f32; 8] {
+        let mut side_weights = [0.0; 8];
+        for (index, side) in SpawnSide::ALL.iter().enumerate() {
+            if let Some((_, weight)) = self.weights.iter().find(|(weight_side, _)| weight_side == side) {
+                side_weights[index] = weight.max(0.);
+            }
+        }
+        side_weights
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct SpawnSideWeightsLoader;
+
+impl AssetLoader for SpawnSideWeightsLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let side_weights = ron::de::from_bytes::<SpawnSideWeights>(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(side_weights));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["spawns.ron"]
+    }
+}
@@ -1,14 +1,23 @@
 ﻿use bevy::prelude::*;
 use iyes_loopless::fixedtimestep::*;
 use iyes_loopless::prelude::*;
+use rand::distributions::WeightedIndex;
 use rand::prelude::*;
 use std::time::Duration;
 use bevy::time::FixedTimesteps;
+use serde::Deserialize;
 
+use crate::enemy::enemy_archetypes::EnemyArchetypes;
 use crate::enemy::enemy_difficulty::EnemyStats;
+use crate::enemy::enemy_spawn_sides::{SpawnSideWeights, SpawnSideWeightsLoader};
+use crate::enemy::enemy_wave_script::{
+    is_wave_script_finished, WaveScript, WaveScriptLoader, WaveScriptPlayer,
+};
 use crate::enemy::{Enemy, VisibilityTimer};
+use crate::game_systems::{GameRng, PlayField};
+use crate::player::planets::Planets;
 use crate::sound::SoundEffectEvents;
-use crate::{AssetHolder, GameState};
+use crate::{AssetHolder, GameState, RestartGameEvent};
 
 pub(crate) struct EnemySpawnerPlugin;
 
@@ -18,7 +27,9 @@ impl Plugin for EnemySpawnerPlugin {
         fixed_update.add_system(
             spawn_next_wave
                 // only do it in-game
-                .run_in_state(GameState::Playing),
+                .run_in_state(GameState::Playing)
+                // the wave script does the spawning until it runs out
+                .run_if(is_wave_script_finished),
         );
         fixed_update.add_system(
             update_timestep
@@ -26,8 +37,13 @@ impl Plugin for EnemySpawnerPlugin {
                 .run_in_state(GameState::Playing),
         );
         app.init_resource::<SpawnRes>()
+            .init_resource::<WaveScriptPlayer>()
+            .add_asset::<WaveScript>()
+            .init_asset_loader::<WaveScriptLoader>()
+            .add_asset::<SpawnSideWeights>()
+            .init_asset_loader::<SpawnSideWeightsLoader>()
             .add_event::<NewSpawnEvent>()
-            .add_enter_system(GameState::GameSetupOnce, setup_spawn_res)
+            .add_enter_system(GameState::GameSetupOnce, setup_spawn_side_weights)
             .add_exit_system(GameState::GameSetupOnce, setup_warning_sprites);
         app.add_stage_before(
             CoreStage::Update,
@@ -42,49 +58,96 @@ impl Plugin for EnemySpawnerPlugin {
                 //.after("missile_post")
                 .with_system(handle_spawn_events.run_on_event::<NewSpawnEvent>())
                 .with_system(handle_visibility_timers)
+                .with_system(play_wave_script)
+                .with_system(update_pressure_fronts.run_if(is_wave_script_finished))
+                .into(),
+        );
+
+        app.add_system_set(
+            ConditionSet::new()
+                .with_system(handle_restart_game_events.run_on_event::<RestartGameEvent>())
                 .into(),
         );
+        app.add_system(update_spawn_bounds.run_not_in_state(GameState::AssetLoading));
+        // picks up edits to the wave script while the game is running (watch_for_changes)
+        app.add_system(
+            reload_wave_script
+                .run_not_in_state(GameState::AssetLoading)
+                .run_on_event::<AssetEvent<WaveScript>>(),
+        );
+        // and to the side weights file
+        app.add_system(
+            reload_spawn_side_weights
+                .run_not_in_state(GameState::AssetLoading)
+                .run_on_event::<AssetEvent<SpawnSideWeights>>(),
+        );
     }
 }
 
 // we need to get the window height and then use that to calculate how far left, right, top, and down are the out of bounds zones
 //using that we can then have a function that returns a new random spawn spot based on that info in the resource
-#[derive(Component, Copy, Clone, PartialEq, Eq, Debug)]
-enum SpawnSide {
+#[derive(Component, Copy, Clone, PartialEq, Eq, Debug, Deserialize)]
+pub(crate) enum SpawnSide {
     Left,
     Top,
     Right,
     Bottom,
+    TopLeft,
+    TopRight,
+    BottomRight,
+    BottomLeft,
 }
 
 impl SpawnSide {
+    pub(crate) const ALL: [SpawnSide; 8] = [
+        SpawnSide::Left,
+        SpawnSide::Top,
+        SpawnSide::Right,
+        SpawnSide::Bottom,
+        SpawnSide::TopLeft,
+        SpawnSide::TopRight,
+        SpawnSide::BottomRight,
+        SpawnSide::BottomLeft,
+    ];
+
+    /// The two sides next to this one going around the play field
+    fn neighbours(&self) -> [SpawnSide; 2] {
+        match self {
+            SpawnSide::Left => [SpawnSide::BottomLeft, SpawnSide::TopLeft],
+            SpawnSide::Top => [SpawnSide::TopLeft, SpawnSide::TopRight],
+            SpawnSide::Right => [SpawnSide::TopRight, SpawnSide::BottomRight],
+            SpawnSide::Bottom => [SpawnSide::BottomRight, SpawnSide::BottomLeft],
+            SpawnSide::TopLeft => [SpawnSide::Left, SpawnSide::Top],
+            SpawnSide::TopRight => [SpawnSide::Top, SpawnSide::Right],
+            SpawnSide::BottomRight => [SpawnSide::Right, SpawnSide::Bottom],
+            SpawnSide::BottomLeft => [SpawnSide::Bottom, SpawnSide::Left],
+        }
+    }
+
     fn spawn_warning_object(
         &self,
         sprites: &Res<AssetHolder>,
         spawn_res: &Res<SpawnRes>,
         mut commands: &mut Commands,
     ) {
-        let mut new_spawn_location = Vec2 { x: 0.0, y: 0.0 };
-        let mut spawn_side = SpawnSide::Left;
+        commands.spawn_bundle(WarningBundle::new(sprites, self.warning_location(spawn_res), *self));
+    }
+
+    fn warning_location(&self, spawn_res: &SpawnRes) -> Vec2 {
+        let left = Vec2::new(spawn_res.left + 330., 0.);
+        let top = Vec2::new(0., spawn_res.top - 340.);
+        let right = Vec2::new(spawn_res.right - 330., 0.);
+        let bottom = Vec2::new(0., spawn_res.bottom + 340.);
         match self {
-            SpawnSide::Left => {
-                new_spawn_location.x = spawn_res.left + 330.;
-                spawn_side = SpawnSide::Left;
-            }
-            SpawnSide::Top => {
-                new_spawn_location.y = spawn_res.top - 340.;
-                spawn_side = SpawnSide::Top;
-            }
-            SpawnSide::Right => {
-                new_spawn_location.x = spawn_res.right - 330.;
-                spawn_side = SpawnSide::Right;
-            }
-            SpawnSide::Bottom => {
-                new_spawn_location.y = spawn_res.bottom + 340.;
-                spawn_side = SpawnSide::Bottom;
-            }
+            SpawnSide::Left => left,
+            SpawnSide::Top => top,
+            SpawnSide::Right => right,
+            SpawnSide::Bottom => bottom,
+            SpawnSide::TopLeft => left + top,
+            SpawnSide::TopRight => right + top,
+            SpawnSide::BottomRight => right + bottom,
+            SpawnSide::BottomLeft => left + bottom,
         }
-        commands.spawn_bundle(WarningBundle::new(sprites, new_spawn_location, spawn_side));
     }
 }
 
@@ -128,6 +191,13 @@ impl WarningBundle {
 
 struct NewSpawnEvent(SpawnSide);
 
+/// A stretch of time where most spawns come from one or two neighbouring sides
+struct PressureFront {
+    sides: Vec<SpawnSide>,
+    weight_multiplier: f32,
+    timer: Timer,
+}
+
 struct SpawnRes {
     left: f32,
     top: f32,
@@ -135,6 +205,14 @@ struct SpawnRes {
     bottom: f32,
 
     space: f32,
+    // how far along each edge from the corner the corner zones reach
+    corner_spread: f32,
+
+    // base chance of each side being picked, in SpawnSide::ALL order. Set from assets/data/default.spawns.ron
+    side_weights: [f32; 8],
+    pressure_front: Option<PressureFront>,
+    // counts down to the next pressure front while there is none
+    time_till_next_front: f32,
 }
 
 impl Default for SpawnRes {
@@ -146,66 +224,106 @@ impl Default for SpawnRes {
             bottom: 0.0,
 
             space: 10.0,
+            corner_spread: 150.0,
+
+            // used until the side weights file has loaded
+            side_weights: [1.0, 1.0, 1.0, 1.0, 0.5, 0.5, 0.5, 0.5],
+            pressure_front: None,
+            time_till_next_front: 30.0,
         }
     }
 }
 
 impl SpawnRes {
-    fn new_spawn_point(&self) -> (Vec2, SpawnSide) {
-        let mut rng = thread_rng();
-        let random = rng.gen_range(0..5);
-        let mut new_spawn_location = Vec2 { x: 0.0, y: 0.0 };
-        let mut spawn_side = SpawnSide::Left;
-        match random {
-            1 => {
-                // left
-                let range = rng.gen_range(self.bottom..self.top);
-                new_spawn_location = Vec2 {
-                    x: self.left - 50.,
-                    y: range,
-                };
-                spawn_side = SpawnSide::Left;
-            }
-            2 => {
-                // top
-                let range = rng.gen_range(self.left..self.right);
-                new_spawn_location = Vec2 {
-                    x: range,
-                    y: self.top + 50.,
-                };
-                spawn_side = SpawnSide::Top;
-            }
-            3 => {
-                // right
-                let range = rng.gen_range(self.bottom..self.top);
-                new_spawn_location = Vec2 {
-                    x: self.right + 50.,
-                    y: range,
-                };
-                spawn_side = SpawnSide::Right;
-            }
-            _ => {
-                // bottom and all else
-                let range = rng.gen_range(self.left..self.right);
-                new_spawn_location = Vec2 {
-                    x: range,
-                    y: self.bottom - 50.,
-                };
-                spawn_side = SpawnSide::Bottom;
+    fn is_hot_side(&self, spawn_side: SpawnSide) -> bool {
+        match &self.pressure_front {
+            Some(pressure_front) => pressure_front.sides.contains(&spawn_side),
+            None => false,
+        }
+    }
+
+    /// The side weights with the current pressure front applied
+    fn current_side_weights(&self) -> [f32; 8] {
+        let mut side_weights = self.side_weights;
+        if let Some(pressure_front) = &self.pressure_front {
+            for (index, side) in SpawnSide::ALL.iter().enumerate() {
+                if pressure_front.sides.contains(side) {
+                    side_weights[index] *= pressure_front.weight_multiplier;
+                }
             }
         }
+        side_weights
+    }
 
-        (new_spawn_location, spawn_side)
+    fn new_spawn_point(&self, rng: &mut impl Rng) -> (Vec2, SpawnSide) {
+        let spawn_side = match WeightedIndex::new(self.current_side_weights()) {
+            Ok(distribution) => SpawnSide::ALL[distribution.sample(rng)],
+            // every weight was set to zero, fall back to an even spread over the four sides
+            Err(_) => SpawnSide::ALL[rng.gen_range(0..4)],
+        };
+
+        (self.spawn_point_on_side(spawn_side, rng), spawn_side)
+    }
+
+    fn spawn_point_on_side(&self, spawn_side: SpawnSide, rng: &mut impl Rng) -> Vec2 {
+        match spawn_side {
+            SpawnSide::Left => Vec2 {
+                x: self.left - 50.,
+                y: rng.gen_range(self.bottom..self.top),
+            },
+            SpawnSide::Top => Vec2 {
+                x: rng.gen_range(self.left..self.right),
+                y: self.top + 50.,
+            },
+            SpawnSide::Right => Vec2 {
+                x: self.right + 50.,
+                y: rng.gen_range(self.bottom..self.top),
+            },
+            SpawnSide::Bottom => Vec2 {
+                x: rng.gen_range(self.left..self.right),
+                y: self.bottom - 50.,
+            },
+            SpawnSide::TopLeft => self.corner_spawn_point(Vec2::new(-1., 1.), rng),
+            SpawnSide::TopRight => self.corner_spawn_point(Vec2::new(1., 1.), rng),
+            SpawnSide::BottomRight => self.corner_spawn_point(Vec2::new(1., -1.), rng),
+            SpawnSide::BottomLeft => self.corner_spawn_point(Vec2::new(-1., -1.), rng),
+        }
+    }
+
+    /// A point just outside the corner in the given direction, pulled in along one of the two edges
+    fn corner_spawn_point(&self, direction: Vec2, rng: &mut impl Rng) -> Vec2 {
+        let corner = Vec2::new(
+            if direction.x < 0. { self.left } else { self.right },
+            if direction.y < 0. { self.bottom } else { self.top },
+        ) + direction * 50.;
+        let pull_in = rng.gen_range(0.0..self.corner_spread);
+        if rng.gen_bool(0.5) {
+            corner - Vec2::new(direction.x * pull_in, 0.)
+        } else {
+            corner - Vec2::new(0., direction.y * pull_in)
+        }
     }
 }
 
-fn setup_spawn_res(mut spawn_res: ResMut<SpawnRes>, windows: Res<Windows>) {
-    let wnd = windows.get_primary().unwrap();
-    let virtual_gameplay_size = Vec2::new(wnd.height() as f32, wnd.height() as f32);
+/// Keeps the spawn ring and the warnings just outside the play field whenever it changes size
+fn update_spawn_bounds(
+    play_field: Res<PlayField>,
+    mut spawn_res: ResMut<SpawnRes>,
+    mut warnings: Query<(&mut Transform, &SpawnSide), With<Warning>>,
+) {
+    if !play_field.is_changed() {
+        return;
+    }
+    let virtual_gameplay_size = Vec2::new(play_field.arena_size, play_field.arena_size);
     spawn_res.left = -(virtual_gameplay_size.x / 2. + spawn_res.space);
     spawn_res.top = virtual_gameplay_size.x / 2. + spawn_res.space;
     spawn_res.right = virtual_gameplay_size.x / 2. + spawn_res.space;
     spawn_res.bottom = -(virtual_gameplay_size.x / 2. + spawn_res.space);
+
+    for (mut transform, spawn_side) in warnings.iter_mut() {
+        let warning_location = spawn_side.warning_location(&spawn_res);
+        transform.translation = warning_location.extend(transform.translation.z);
+    }
 }
 
 fn setup_warning_sprites(
@@ -213,10 +331,43 @@ fn setup_warning_sprites(
     spawn_res: Res<SpawnRes>,
     mut commands: Commands,
 ) {
-    SpawnSide::spawn_warning_object(&SpawnSide::Left, &sprites, &spawn_res, &mut commands);
-    SpawnSide::spawn_warning_object(&SpawnSide::Top, &sprites, &spawn_res, &mut commands);
-    SpawnSide::spawn_warning_object(&SpawnSide::Right, &sprites, &spawn_res, &mut commands);
-    SpawnSide::spawn_warning_object(&SpawnSide::Bottom, &sprites, &spawn_res, &mut commands);
+    for spawn_side in SpawnSide::ALL.iter() {
+        spawn_side.spawn_warning_object(&sprites, &spawn_res, &mut commands);
+    }
+}
+
+/// Rolls a new pressure front every so often once the procedural waves have started
+fn update_pressure_fronts(
+    mut spawn_res: ResMut<SpawnRes>,
+    mut game_rng: ResMut<GameRng>,
+    time: Res<Time>,
+) {
+    let rng = &mut game_rng.rng;
+    if let Some(pressure_front) = &mut spawn_res.pressure_front {
+        pressure_front.timer.tick(time.delta());
+        if pressure_front.timer.finished() {
+            spawn_res.pressure_front = None;
+            spawn_res.time_till_next_front = rng.gen_range(20.0..40.0);
+        }
+        return;
+    }
+
+    spawn_res.time_till_next_front -= time.delta_seconds();
+    if spawn_res.time_till_next_front > 0. {
+        return;
+    }
+    let main_side = SpawnSide::ALL[rng.gen_range(0..SpawnSide::ALL.len())];
+    let mut sides = vec![main_side];
+    // sometimes the front spreads over the neighbouring side as well
+    if rng.gen_bool(0.5) {
+        sides.push(main_side.neighbours()[rng.gen_range(0..2)]);
+    }
+    info!("pressure front from {:?}", sides);
+    spawn_res.pressure_front = Some(PressureFront {
+        sides,
+        weight_multiplier: 6.0,
+        timer: Timer::new(Duration::from_secs_f32(rng.gen_range(15.0..25.0)), false),
+    });
 }
 
 fn update_timestep(mut timesteps: ResMut<FixedTimestepInfo>, enemy_stats: Res<EnemyStats>) {
@@ -226,30 +377,183 @@ fn update_timestep(mut timesteps: ResMut<FixedTimestepInfo>, enemy_stats: Res<En
 fn spawn_next_wave(
     sprites: Res<AssetHolder>,
     spawn_res: Res<SpawnRes>,
+    enemy_archetypes: Res<EnemyArchetypes>,
+    planets: Res<Planets>,
     mut enemy_stats: ResMut<EnemyStats>,
+    mut game_rng: ResMut<GameRng>,
     mut commands: Commands,
     mut spawn_event_writer: EventWriter<NewSpawnEvent>,
 ) {
+    // the normal microwaves take a break while a boss is out
+    if enemy_stats.is_boss_alive {
+        return;
+    }
+    let rng = &mut game_rng.rng;
     for i in 0..enemy_stats.amount_to_spawn_in_microwave {
-        let (new_spawn_point, spawn_side) = &spawn_res.new_spawn_point();
-        Enemy::spawn(&sprites, &enemy_stats, &mut commands, new_spawn_point);
+        let (new_spawn_point, spawn_side) = &spawn_res.new_spawn_point(rng);
+        let archetype = enemy_archetypes.pick_archetype(enemy_stats.difficulty_level, rng);
+        // a group all goes for the same planet
+        let target = planets.pick_target(rng);
+        // groups spawn bunched up around the spawn point
+        for group_index in 0..archetype.group_size.max(1) {
+            let mut group_spawn_point = *new_spawn_point;
+            if group_index > 0 {
+                group_spawn_point += Vec2 {
+                    x: rng.gen_range(-30.0..30.0),
+                    y: rng.gen_range(-30.0..30.0),
+                };
+            }
+            Enemy::spawn(
+                &sprites,
+                &archetype,
+                enemy_stats.speed,
+                &mut commands,
+                &group_spawn_point,
+                target,
+            );
+            enemy_stats.all_time_enemy_count += 1;
+        }
         spawn_event_writer.send(NewSpawnEvent(*spawn_side));
+    }
+}
+
+fn play_wave_script(
+    sprites: Res<AssetHolder>,
+    spawn_res: Res<SpawnRes>,
+    enemy_archetypes: Res<EnemyArchetypes>,
+    wave_scripts: Res<Assets<WaveScript>>,
+    planets: Res<Planets>,
+    mut wave_script_player: ResMut<WaveScriptPlayer>,
+    mut enemy_stats: ResMut<EnemyStats>,
+    mut game_rng: ResMut<GameRng>,
+    mut commands: Commands,
+    mut spawn_event_writer: EventWriter<NewSpawnEvent>,
+    time: Res<Time>,
+) {
+    if wave_script_player.is_finished {
+        return;
+    }
+    let wave_script = match wave_scripts.get(&sprites.wave_script) {
+        Some(wave_script) => wave_script,
+        None => {
+            wave_script_player.is_finished = true;
+            return;
+        }
+    };
+    let rng = &mut game_rng.rng;
+
+    wave_script_player.elapsed += time.delta_seconds();
+    while let Some(wave) = wave_script.waves.get(wave_script_player.next_wave) {
+        if wave.time > wave_script_player.elapsed {
+            break;
+        }
+        wave_script_player.queue_wave(
+            wave,
+            &enemy_archetypes,
+            enemy_stats.difficulty_level,
+            enemy_stats.speed,
+            rng,
+        );
+        wave_script_player.next_wave += 1;
+    }
+
+    for pending_spawn in wave_script_player.pending.iter_mut() {
+        pending_spawn.time_left -= time.delta_seconds();
+    }
+    let (ready, waiting): (Vec<_>, Vec<_>) = std::mem::take(&mut wave_script_player.pending)
+        .into_iter()
+        .partition(|pending_spawn| pending_spawn.time_left <= 0.);
+    wave_script_player.pending = waiting;
+
+    for pending_spawn in ready.iter() {
+        let (spawn_point, spawn_side) = match pending_spawn.side {
+            Some(side) => (spawn_res.spawn_point_on_side(side, rng), side),
+            None => spawn_res.new_spawn_point(rng),
+        };
+        Enemy::spawn(
+            &sprites,
+            &pending_spawn.archetype,
+            pending_spawn.speed,
+            &mut commands,
+            &spawn_point,
+            planets.pick_target(rng),
+        );
+        spawn_event_writer.send(NewSpawnEvent(spawn_side));
         enemy_stats.all_time_enemy_count += 1;
     }
+
+    if wave_script_player.next_wave >= wave_script.waves.len() && wave_script_player.pending.is_empty() {
+        info!("wave script finished, switching to procedural waves");
+        wave_script_player.is_finished = true;
+    }
+}
+
+fn reload_wave_script(
+    mut asset_events: EventReader<AssetEvent<WaveScript>>,
+    sprites: Res<AssetHolder>,
+    wave_scripts: Res<Assets<WaveScript>>,
+    mut wave_script_player: ResMut<WaveScriptPlayer>,
+) {
+    for event in asset_events.iter() {
+        if let AssetEvent::Modified { handle } = event {
+            if *handle == sprites.wave_script {
+                if let Some(wave_script) = wave_scripts.get(handle) {
+                    wave_script_player.rewind_to_elapsed(wave_script);
+                }
+            }
+        }
+    }
+}
+
+fn setup_spawn_side_weights(
+    sprites: Res<AssetHolder>,
+    side_weight_files: Res<Assets<SpawnSideWeights>>,
+    mut spawn_res: ResMut<SpawnRes>,
+) {
+    if let Some(side_weights) = side_weight_files.get(&sprites.spawn_side_weights) {
+        spawn_res.side_weights = side_weights.to_array();
+    }
+}
+
+fn reload_spawn_side_weights(
+    mut asset_events: EventReader<AssetEvent<SpawnSideWeights>>,
+    sprites: Res<AssetHolder>,
+    side_weight_files: Res<Assets<SpawnSideWeights>>,
+    mut spawn_res: ResMut<SpawnRes>,
+) {
+    for event in asset_events.iter() {
+        if let AssetEvent::Modified { handle } = event {
+            if *handle == sprites.spawn_side_weights {
+                if let Some(side_weights) = side_weight_files.get(handle) {
+                    spawn_res.side_weights = side_weights.to_array();
+                }
+            }
+        }
+    }
+}
+
+fn handle_restart_game_events(mut commands: Commands, mut spawn_res: ResMut<SpawnRes>) {
+    commands.insert_resource(WaveScriptPlayer::default());
+    spawn_res.pressure_front = None;
+    spawn_res.time_till_next_front = SpawnRes::default().time_till_next_front;
 }
 
 fn handle_spawn_events(
     mut spawn_event_reader: EventReader<NewSpawnEvent>,
+    spawn_res: Res<SpawnRes>,
     mut timed_enemies: Query<
-        (Entity, &mut VisibilityTimer, &mut Visibility, &SpawnSide),
+        (Entity, &mut VisibilityTimer, &mut Visibility, &mut Sprite, &SpawnSide),
         With<Warning>,
     >,
     mut sound_effect_writer: EventWriter<SoundEffectEvents>,
 ) {
     for event in spawn_event_reader.iter() {
-        for (entity, mut visibility_timer, mut visibility, spawn_side) in timed_enemies.iter_mut() {
+        for (entity, mut visibility_timer, mut visibility, mut sprite, spawn_side) in
+        timed_enemies.iter_mut()
+        {
             if *spawn_side == event.0 {
                 *visibility = Visibility { is_visible: true };
+                sprite.color = warning_color(spawn_res.is_hot_side(*spawn_side)).with_a(1.0);
                 visibility_timer.visibility_timer.reset();
                 sound_effect_writer.send(SoundEffectEvents::EnemySpawnWarning);
             }
@@ -258,14 +562,33 @@ fn handle_spawn_events(
 }
 
 fn handle_visibility_timers(
-    mut timed_enemies: Query<(Entity, &mut VisibilityTimer, &mut Visibility), With<Warning>>,
+    spawn_res: Res<SpawnRes>,
+    mut timed_enemies: Query<
+        (Entity, &mut VisibilityTimer, &mut Visibility, &mut Sprite, &SpawnSide),
+        With<Warning>,
+    >,
     mut commands: Commands,
     time: Res<Time>,
 ) {
-    for (entity, mut visibility_timer, mut visibility) in timed_enemies.iter_mut() {
+    for (entity, mut visibility_timer, mut visibility, mut sprite, spawn_side) in
+    timed_enemies.iter_mut()
+    {
         visibility_timer.visibility_timer.tick(time.delta());
         if visibility_timer.visibility_timer.finished() {
-            *visibility = Visibility { is_visible: false };
+            // warnings on the sides of a pressure front stay up faintly until it passes
+            let is_hot_side = spawn_res.is_hot_side(*spawn_side);
+            *visibility = Visibility {
+                is_visible: is_hot_side,
+            };
+            sprite.color = warning_color(is_hot_side).with_a(0.35);
         }
     }
 }
+
+fn warning_color(is_hot_side: bool) -> Color {
+    if is_hot_side {
+        Color::ORANGE
+    } else {
+        Color::WHITE
+    }
+}
@@ -0,0 +1,131 @@
+use crate::enemy::enemy_archetypes::{EnemyArchetype, EnemyArchetypes};
+use crate::enemy::enemy_movement::MovementPattern;
+use crate::enemy::enemy_spawner::SpawnSide;
+use bevy::asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy::prelude::*;
+use bevy::reflect::TypeUuid;
+use bevy::utils::BoxedFuture;
+use serde::Deserialize;
+
+/// A list of timed waves played at the start of a run before the procedural difficulty curve takes over.
+/// Waves have to be listed in time order
+#[derive(Deserialize, TypeUuid)]
+#[uuid = "a3b6f0d2-64e1-4c8f-b1a7-2f9e5c3d8e14"]
+pub(crate) struct WaveScript {
+    pub(crate) waves: Vec<ScriptedWave>,
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(default)]
+pub(crate) struct ScriptedWave {
+    // seconds since the run started
+    pub(crate) time: f32,
+    // sides are cycled through for each enemy, an empty list picks random sides
+    pub(crate) sides: Vec<SpawnSide>,
+    pub(crate) count: u32,
+    // archetype name from the archetype file, None picks from the spawn tables like normal
+    pub(crate) archetype: Option<String>,
+    // seconds between each enemy of the wave
+    pub(crate) spacing: f32,
+    // overrides the base enemy speed, the archetype speed multiplier still applies
+    pub(crate) speed: Option<f32>,
+    pub(crate) movement: Option<MovementPattern>,
+}
+
+impl Default for ScriptedWave {
+    fn default() -> Self {
+        ScriptedWave {
+            time: 0.0,
+            sides: vec![],
+            count: 1,
+            archetype: None,
+            spacing: 0.5,
+            speed: None,
+            movement: None,
+        }
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct WaveScriptLoader;
+
+impl AssetLoader for WaveScriptLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let wave_script = ron::de::from_bytes::<WaveScript>(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(wave_script));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["waves.ron"]
+    }
+}
+
+/// An enemy from a scripted wave waiting for its spacing delay to run out
+pub(crate) struct PendingSpawn {
+    pub(crate) time_left: f32,
+    pub(crate) side: Option<SpawnSide>,
+    pub(crate) archetype: EnemyArchetype,
+    pub(crate) speed: f32,
+}
+
+/// Tracks how far into the wave script the current run is
+#[derive(Default)]
+pub(crate) struct WaveScriptPlayer {
+    pub(crate) elapsed: f32,
+    pub(crate) next_wave: usize,
+    pub(crate) pending: Vec<PendingSpawn>,
+    pub(crate) is_finished: bool,
+}
+
+impl WaveScriptPlayer {
+    pub(crate) fn queue_wave(
+        &mut self,
+        wave: &ScriptedWave,
+        enemy_archetypes: &Res<EnemyArchetypes>,
+        difficulty_level: u32,
+        base_speed: f32,
+        rng: &mut impl rand::Rng,
+    ) {
+        for i in 0..wave.count {
+            let mut archetype = match &wave.archetype {
+                Some(name) => enemy_archetypes.get(name).cloned().unwrap_or_default(),
+                None => enemy_archetypes.pick_archetype(difficulty_level, rng),
+            };
+            if let Some(movement) = wave.movement {
+                archetype.movement = movement;
+            }
+            let side = if wave.sides.is_empty() {
+                None
+            } else {
+                Some(wave.sides[i as usize % wave.sides.len()])
+            };
+            self.pending.push(PendingSpawn {
+                time_left: wave.spacing * i as f32,
+                side,
+                archetype,
+                speed: wave.speed.unwrap_or(base_speed),
+            });
+        }
+    }
+
+    /// Points the player at the first wave after the current time. Used when the script is hot reloaded
+    pub(crate) fn rewind_to_elapsed(&mut self, wave_script: &WaveScript) {
+        self.next_wave = wave_script
+            .waves
+            .iter()
+            .position(|wave| wave.time > self.elapsed)
+            .unwrap_or(wave_script.waves.len());
+        self.is_finished = false;
+    }
+}
+
+pub(crate) fn is_wave_script_finished(wave_script_player: Res<WaveScriptPlayer>) -> bool {
+    wave_script_player.is_finished
+}
@@ -1,20 +1,38 @@
+pub mod enemy_archetypes;
+pub mod enemy_boss;
 pub mod enemy_difficulty;
+pub mod enemy_ghost_trail;
+pub mod enemy_movement;
+pub mod enemy_spawn_sides;
 pub mod enemy_spawner;
+pub mod enemy_wave_script;
 
+use crate::enemy::enemy_archetypes::{EnemyArchetype, EnemyArchetypePlugin};
+use crate::enemy::enemy_boss::EnemyBossPlugin;
 use crate::enemy::enemy_difficulty::{EnemyDifficultyPlugin, EnemyStats, update_enemy_count};
+use crate::enemy::enemy_ghost_trail::GhostTrailPlugin;
+use crate::enemy::enemy_movement::{EnemyMovement, EnemyMovementPlugin};
 use crate::enemy::enemy_spawner::EnemySpawnerPlugin;
 use crate::{AssetHolder, GameState, PlayerStats, RestartGameEvent, ScoreEvent};
 use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
 use bevy_rapier2d::prelude::*;
 use iyes_loopless::prelude::*;
+use rand::prelude::*;
+use std::f32::consts::TAU;
 use std::time::Duration;
 
 pub(crate) struct EnemyPlugin;
 
 impl Plugin for EnemyPlugin {
     fn build(&self, app: &mut App) {
+        app.add_plugin(EnemyArchetypePlugin);
         app.add_plugin(EnemySpawnerPlugin);
         app.add_plugin(EnemyDifficultyPlugin);
+        app.add_plugin(EnemyMovementPlugin);
+        app.add_plugin(EnemyBossPlugin);
+        app.add_plugin(GhostTrailPlugin);
+        app.add_event::<EnemyKilledEvent>();
         app.add_system_set(
             ConditionSet::new()
                 .with_system(handle_restart_game_events.run_on_event::<RestartGameEvent>())
@@ -28,6 +46,7 @@ impl Plugin for EnemyPlugin {
                 .after("missile_post")
                 .with_system(handle_enemy_scanned)
                 .with_system(handle_visibility_timers)
+                .with_system(handle_hit_flashes)
                 .into(),
         );
         app.add_system_set(
@@ -45,6 +64,7 @@ impl Plugin for EnemyPlugin {
 #[derive(Component)]
 pub(crate) struct Enemy {
     pub(crate) scan_ghost: Entity,
+    pub(crate) score_value: u32,
 }
 
 #[derive(Component)]
@@ -52,47 +72,126 @@ pub(crate) struct VisibilityTimer {
     visibility_timer: Timer,
 }
 
+#[derive(Component)]
+pub(crate) struct EnemyHealth {
+    pub(crate) current: f32,
+    pub(crate) max: f32,
+}
+
+impl EnemyHealth {
+    /// Returns true if this damage killed the enemy
+    pub(crate) fn damage(&mut self, amount: f32) -> bool {
+        self.current -= amount;
+        if self.current <= 0. {
+            self.current = 0.;
+            return true;
+        }
+        return false;
+    }
+}
+
+#[derive(Component)]
+pub(crate) struct HitFlash {
+    flash_timer: Timer,
+}
+
+#[derive(Default)]
+pub struct EnemyKilledEvent {
+    pub(crate) location: Vec2,
+    // 0 for a direct kill, otherwise how many chain blasts deep the killing blast was
+    pub(crate) chain_depth: u32,
+}
+
+/// Marks an enemy killed by a chain reaction blast and how deep in the chain that blast was
+#[derive(Component)]
+pub(crate) struct ChainLink(pub(crate) u32);
+
 impl Enemy {
     pub(crate) fn spawn(
         sprites: &Res<AssetHolder>,
-        enemy_stats: &ResMut<EnemyStats>,
+        archetype: &EnemyArchetype,
+        base_speed: f32,
         mut commands: &mut Commands,
         spawn_location: &Vec2,
-    ) {
+        target: Vec2,
+    ) -> Entity {
         let spawn_location_local = spawn_location.clone();
-        let angle = f32::atan2(-spawn_location_local.y, -spawn_location_local.x);
+        let to_target = target - spawn_location_local;
+        let angle = f32::atan2(to_target.y, to_target.x);
+
+        let speed = base_speed * archetype.speed_multiplier;
+        let movement = EnemyMovement::new(archetype.movement, speed, target);
 
         let missile_rotation = Quat::from_rotation_z(angle);
-        let rotated_velocity = missile_rotation
-            * Vec3 {
-            x: enemy_stats.speed.clone(),
-            y: 0.0,
-            z: 0.0,
-        };
         let ghost_entity = commands
             .spawn_bundle(GhostEnemyBundle::new(
                 &sprites,
                 spawn_location_local.clone(),
             ))
+            .with_children(|parent| {
+                parent
+                    .spawn_bundle(GeometryBuilder::build_as(
+                        &shapes::Circle {
+                            radius: 0.0,
+                            center: default(),
+                        },
+                        DrawMode::Stroke(StrokeMode::new(
+                            Color::Rgba {
+                                red: 0.3,
+                                green: 0.6,
+                                blue: 0.25,
+                                alpha: 0.6,
+                            },
+                            // the ghost is scaled by 3
+                            0.5,
+                        )),
+                        Transform::from_xyz(0., 0., 0.1),
+                    ))
+                    .insert(UncertaintyRing);
+            })
             .id();
-        commands.spawn_bundle(EnemyBundle::new(
-            &sprites,
-            &spawn_location_local,
-            rotated_velocity.truncate(),
-            missile_rotation,
-            ghost_entity,
-        ));
+        commands
+            .spawn_bundle(EnemyBundle::new(
+                &sprites,
+                archetype,
+                &spawn_location_local,
+                movement.velocity(spawn_location_local),
+                missile_rotation,
+                ghost_entity,
+                movement,
+            ))
+            .id()
     }
 }
 
+/// What finished an enemy off
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum KillSource {
+    // missiles, mines, the railgun, chain blasts and satellites
+    Weapon,
+    // blocks and counter pulses. These score but don't count as kills for EnemyKilledEvent
+    Shield,
+}
+
+#[derive(Component)]
+pub(crate) struct Destroyed(pub(crate) KillSource);
+
+/// Marks an enemy that took damage this frame but survived
 #[derive(Component)]
-pub(crate) struct Destroyed;
+pub(crate) struct Damaged;
 
 #[derive(Component)]
 pub(crate) struct ReachedPlanet;
 
+/// Added to an enemy or ghost a scan touched. origin is where that scan started from
+#[derive(Component)]
+pub(crate) struct Scanned {
+    pub(crate) origin: Vec2,
+}
+
+/// The circle around a ghost showing how far off its position might be
 #[derive(Component)]
-pub(crate) struct Scanned;
+pub(crate) struct UncertaintyRing;
 
 #[derive(Bundle)]
 pub struct EnemyBundle {
@@ -101,6 +200,8 @@ pub struct EnemyBundle {
     rigidbody: RigidBody,
     collider: Collider,
     enemy: Enemy,
+    health: EnemyHealth,
+    movement: EnemyMovement,
     gravity_scale: GravityScale,
     velocity: Velocity,
     sensor: Sensor,
@@ -110,11 +211,19 @@ pub struct EnemyBundle {
 impl EnemyBundle {
     pub(crate) fn new(
         sprites: &Res<AssetHolder>,
+        archetype: &EnemyArchetype,
         spawn_location: &Vec2,
         linvel: Vec2,
         rotation: Quat,
         ghost_entity: Entity,
+        movement: EnemyMovement,
     ) -> EnemyBundle {
+        // archetypes that failed to resolve a sprite fall back to the normal enemy sprite
+        let texture = if archetype.texture.is_strong() {
+            archetype.texture.clone()
+        } else {
+            sprites.enemy.clone()
+        };
         EnemyBundle {
             sprite_bundle: SpriteBundle {
                 sprite: Default::default(),
@@ -122,23 +231,29 @@ impl EnemyBundle {
                     translation: spawn_location.extend(10.),
                     rotation,
                     scale: Vec3 {
-                        x: 3.0,
-                        y: 3.0,
+                        x: archetype.scale,
+                        y: archetype.scale,
                         z: 1.0,
                     },
                 },
                 global_transform: Default::default(),
-                texture: sprites.enemy.clone(),
+                texture,
 
                 visibility: Visibility { is_visible: false },
                 computed_visibility: Default::default(),
             },
             rigidbody: RigidBody::Dynamic,
-            collider: Collider::ball(5.),
+            collider: Collider::ball(archetype.collider_radius),
             gravity_scale: GravityScale(0.),
             enemy: Enemy {
                 scan_ghost: ghost_entity,
+                score_value: archetype.score_value,
+            },
+            health: EnemyHealth {
+                current: archetype.hit_points,
+                max: archetype.hit_points,
             },
+            movement,
             velocity: Velocity {
                 linvel,
                 angvel: 0.0,
@@ -189,19 +304,87 @@ impl GhostEnemyBundle {
     }
 }
 
+/// Applies damage to an enemy, marking it Destroyed if it died or Damaged if it survived.
+/// Returns true if this damage killed the enemy
+pub(crate) fn damage_enemy(
+    commands: &mut Commands,
+    enemy_entity: Entity,
+    enemy_health: &mut EnemyHealth,
+    amount: f32,
+    source: KillSource,
+) -> bool {
+    // already dead this frame, don't count the kill twice
+    if enemy_health.current <= 0. {
+        return false;
+    }
+    return if enemy_health.damage(amount) {
+        commands.entity(enemy_entity).insert(Destroyed(source));
+        true
+    } else {
+        commands.entity(enemy_entity).insert(Damaged);
+        false
+    };
+}
+
 pub(crate) fn handle_enemy_collision_changes(
-    mut destroyed_enemies: Query<(Entity, &Enemy), With<Destroyed>>,
-    mut ghost_query: Query<&mut Ghost>,
+    mut destroyed_enemies: Query<(Entity, &Enemy, &Transform, &Destroyed, Option<&ChainLink>)>,
+    mut damaged_enemies: Query<
+        (Entity, &Enemy, &Transform, &mut Sprite, &mut Visibility),
+        (With<Damaged>, Without<Destroyed>),
+    >,
+    mut ghost_query: Query<(&mut Ghost, &mut Transform, Option<&Children>), Without<Enemy>>,
+    mut uncertainty_rings: Query<&mut Path, With<UncertaintyRing>>,
     mut commands: Commands,
     mut score_event_writer: EventWriter<ScoreEvent>,
-    player_stats: Res<PlayerStats>,
+    mut enemy_killed_event_writer: EventWriter<EnemyKilledEvent>,
 ) {
-    for (destroyed_enemy, enemy) in destroyed_enemies.iter_mut() {
-        if let Ok(mut ghost) = ghost_query.get_mut(enemy.scan_ghost) {
+    for (destroyed_enemy, enemy, transform, destroyed, chain_link) in destroyed_enemies.iter_mut() {
+        if let Ok((mut ghost, _, _)) = ghost_query.get_mut(enemy.scan_ghost) {
             ghost.0 = true;
         }
         commands.entity(destroyed_enemy).despawn();
-        score_event_writer.send(ScoreEvent(player_stats.enemy_kill_score));
+        score_event_writer.send(ScoreEvent(enemy.score_value));
+        // only weapon kills feed dying scanners and chain reactions, like before enemies had health
+        if destroyed.0 == KillSource::Weapon {
+            enemy_killed_event_writer.send(EnemyKilledEvent {
+                location: transform.translation.truncate(),
+                chain_depth: chain_link.map_or(0, |chain_link| chain_link.0),
+            });
+        }
+    }
+
+    // enemies that survived a hit flash and give away their position
+    for (damaged_enemy, enemy, transform, mut sprite, mut visibility) in damaged_enemies.iter_mut() {
+        // a hit gives away the exact position
+        if let Ok((_, mut ghost_transform, children)) = ghost_query.get_mut(enemy.scan_ghost) {
+            ghost_transform.translation = transform.translation;
+            set_uncertainty_ring(children, &mut uncertainty_rings, 0.);
+        }
+        sprite.color = Color::RED;
+        *visibility = Visibility { is_visible: true };
+        commands
+            .entity(damaged_enemy)
+            .insert(VisibilityTimer {
+                visibility_timer: Timer::new(Duration::from_secs_f32(1.0), false),
+            })
+            .insert(HitFlash {
+                flash_timer: Timer::new(Duration::from_secs_f32(0.15), false),
+            })
+            .remove::<Damaged>();
+    }
+}
+
+pub(crate) fn handle_hit_flashes(
+    mut flashing_enemies: Query<(Entity, &mut HitFlash, &mut Sprite), With<Enemy>>,
+    mut commands: Commands,
+    time: Res<Time>,
+) {
+    for (entity, mut hit_flash, mut sprite) in flashing_enemies.iter_mut() {
+        hit_flash.flash_timer.tick(time.delta());
+        if hit_flash.flash_timer.finished() {
+            sprite.color = Color::WHITE;
+            commands.entity(entity).remove::<HitFlash>();
+        }
     }
 }
 
@@ -212,21 +395,33 @@ pub(crate) fn handle_enemy_scanned(
             Option<&Enemy>,
             Option<&Ghost>,
             &Transform,
+            &Scanned,
             &mut Visibility,
         ),
-        With<Scanned>,
     >,
-    mut ghost_query: Query<&mut Transform, Without<Scanned>>,
+    mut ghost_query: Query<(&mut Transform, Option<&Children>), Without<Scanned>>,
+    mut uncertainty_rings: Query<&mut Path, With<UncertaintyRing>>,
+    player_stats: Res<PlayerStats>,
     mut commands: Commands,
 ) {
-    for (scanned_enemy, enemy_option, ghost_option, transform, mut visibility) in
+    for (scanned_enemy, enemy_option, ghost_option, transform, scanned, mut visibility) in
     scanned_enemies.iter_mut()
     {
         //handles
         if enemy_option.is_some() {
             let enemy = enemy_option.unwrap();
-            if let Ok(mut ghost_transform) = ghost_query.get_mut(enemy.scan_ghost) {
-                ghost_transform.translation = transform.translation;
+            if let Ok((mut ghost_transform, children)) = ghost_query.get_mut(enemy.scan_ghost) {
+                // the farther the enemy is from where the scan started the less exact the ghost is
+                let distance = transform.translation.truncate().distance(scanned.origin);
+                let uncertainty = distance * player_stats.sensor_accuracy.0;
+                // uses its own rng so scans don't change the seeded enemy spawns
+                let mut rng = thread_rng();
+                let angle = rng.gen_range(0.0..TAU);
+                let offset = Vec2::new(angle.cos(), angle.sin())
+                    * uncertainty
+                    * rng.gen::<f32>().sqrt();
+                ghost_transform.translation = transform.translation + offset.extend(0.);
+                set_uncertainty_ring(children, &mut uncertainty_rings, uncertainty);
             }
             *visibility = Visibility { is_visible: true };
             commands.entity(scanned_enemy).insert(VisibilityTimer {
@@ -238,7 +433,7 @@ pub(crate) fn handle_enemy_scanned(
         if ghost_option.is_some() {
             let ghost = ghost_option.unwrap();
             if ghost.0 == true {
-                commands.entity(scanned_enemy).despawn();
+                commands.entity(scanned_enemy).despawn_recursive();
             } else {
                 commands.entity(scanned_enemy).remove::<Scanned>();
             }
@@ -246,6 +441,25 @@ pub(crate) fn handle_enemy_scanned(
     }
 }
 
+/// Resizes the ring under a ghost to radius in world units
+fn set_uncertainty_ring(
+    children: Option<&Children>,
+    uncertainty_rings: &mut Query<&mut Path, With<UncertaintyRing>>,
+    radius: f32,
+) {
+    if let Some(children) = children {
+        for child in children.iter() {
+            if let Ok(mut path) = uncertainty_rings.get_mut(*child) {
+                // the ghost is scaled by 3 so the ring is drawn in its local space
+                *path = ShapePath::build_as(&shapes::Circle {
+                    radius: radius / 3.,
+                    center: default(),
+                });
+            }
+        }
+    }
+}
+
 pub(crate) fn handle_visibility_timers(
     mut timed_enemies: Query<(Entity, &mut VisibilityTimer, &mut Visibility), With<Enemy>>,
     mut commands: Commands,
@@ -271,7 +485,7 @@ fn handle_restart_game_events(
         commands.entity(enemy).despawn();
     }
     for enemy in ghosts.iter_mut() {
-        commands.entity(enemy).despawn();
+        commands.entity(enemy).despawn_recursive();
     }
     commands.insert_resource(EnemyStats::default());
 }
@@ -1,15 +1,148 @@
 ﻿use bevy::prelude::*;
-use iyes_loopless::prelude::AppLooplessStateExt;
-use crate::GameState;
+use bevy::window::{WindowId, WindowResized};
+use iyes_loopless::prelude::*;
+use rand::prelude::*;
+use rand::rngs::StdRng;
+use crate::{GameSettings, GameState, RestartGameEvent};
 
 pub struct GameSystems;
 
 impl Plugin for GameSystems {
     fn build(&self, app: &mut App) {
-        app.add_enter_system(GameState::GameSetupOnce, setup_game_systems);
+        app.init_resource::<GameRng>()
+            .init_resource::<PlayField>()
+            .add_enter_system(GameState::GameSetupOnce, setup_game_systems)
+            .add_system(handle_restart_game_events.run_on_event::<RestartGameEvent>())
+            .add_system(update_play_field.run_on_event::<WindowResized>())
+            .add_system(update_camera_scale.run_not_in_state(GameState::AssetLoading));
     }
 }
 
-fn setup_game_systems(mut commands: Commands) {
-    commands.spawn_bundle(Camera2dBundle::default());
+/// The rng every spawn and difficulty decision goes through so a run can be replayed from its seed
+pub(crate) struct GameRng {
+    pub(crate) seed: u64,
+    pub(crate) rng: StdRng,
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        let seed = thread_rng().gen();
+        GameRng {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl GameRng {
+    pub(crate) fn reseed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+}
+
+/// The square in the middle of the window the game is played in. The ui panels fill the space on either side.
+/// The game world is always arena_size units across no matter how big the square is on screen
+pub(crate) struct PlayField {
+    pub(crate) window_size: Vec2,
+    // side length of the square in screen pixels
+    pub(crate) size: f32,
+    // side length of the square in world units
+    pub(crate) arena_size: f32,
+}
+
+impl Default for PlayField {
+    fn default() -> Self {
+        PlayField {
+            window_size: Vec2::ZERO,
+            size: 0.0,
+            arena_size: 1000.0,
+        }
+    }
+}
+
+impl PlayField {
+    fn resize(&mut self, width: f32, height: f32) {
+        self.window_size = Vec2::new(width, height);
+        self.size = height.min(width);
+    }
+
+    /// World units per screen pixel, used as the camera projection scale
+    pub(crate) fn world_scale(&self) -> f32 {
+        if self.size <= 0. {
+            return 1.0;
+        }
+        self.arena_size / self.size
+    }
+
+    pub(crate) fn side_panel_width(&self) -> f32 {
+        (self.window_size.x - self.size) / 2.
+    }
+
+    /// The empty band above and below the field when the window is taller than it is wide
+    pub(crate) fn vertical_offset(&self) -> f32 {
+        (self.window_size.y - self.size) / 2.
+    }
+
+    pub(crate) fn contains_screen_pos(&self, screen_pos: Vec2) -> bool {
+        screen_pos.x >= self.side_panel_width()
+            && screen_pos.x <= self.side_panel_width() + self.size
+            && screen_pos.y >= self.vertical_offset()
+            && screen_pos.y <= self.vertical_offset() + self.size
+    }
+}
+
+/// Days since the unix epoch in UTC, the same for every player on the same day
+pub(crate) fn daily_seed() -> u64 {
+    #[cfg(not(target_arch = "wasm32"))]
+    let millis = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as f64)
+        .unwrap_or(0.);
+    // SystemTime isn't available in the browser
+    #[cfg(target_arch = "wasm32")]
+    let millis = js_sys::Date::now();
+
+    (millis / 86_400_000.).floor() as u64
+}
+
+fn setup_game_systems(
+    mut commands: Commands,
+    windows: Res<Windows>,
+    mut play_field: ResMut<PlayField>,
+) {
+    let wnd = windows.get_primary().unwrap();
+    play_field.resize(wnd.width(), wnd.height());
+
+    let mut camera_bundle = Camera2dBundle::default();
+    camera_bundle.projection.scale = play_field.world_scale();
+    commands.spawn_bundle(camera_bundle);
+}
+
+fn update_play_field(
+    mut resize_events: EventReader<WindowResized>,
+    mut play_field: ResMut<PlayField>,
+) {
+    for event in resize_events.iter() {
+        if event.id == WindowId::primary() {
+            play_field.resize(event.width, event.height);
+        }
+    }
+}
+
+fn update_camera_scale(
+    play_field: Res<PlayField>,
+    mut projections: Query<&mut OrthographicProjection, With<Camera2d>>,
+) {
+    if !play_field.is_changed() {
+        return;
+    }
+    for mut projection in projections.iter_mut() {
+        projection.scale = play_field.world_scale();
+    }
+}
+
+fn handle_restart_game_events(mut game_rng: ResMut<GameRng>, game_settings: Res<GameSettings>) {
+    game_rng.reseed(game_settings.run_seed());
+    info!("new run seeded with {}", game_rng.seed);
 }
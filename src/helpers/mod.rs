@@ -1,9 +1,11 @@
 ﻿use bevy::prelude::*;
 use bevy::render::camera::RenderTarget;
+use crate::game_systems::PlayField;
 
 pub(crate) fn mouse_virtual_play_field_check(
     windows: &Res<Windows>,
     camera_query: &Query<(&Camera, &GlobalTransform)>,
+    play_field: &Res<PlayField>,
 ) -> bool {
     let (camera, _camera_transform) = camera_query.single();
     let wnd = if let RenderTarget::Window(id) = camera.target {
@@ -12,22 +14,15 @@ pub(crate) fn mouse_virtual_play_field_check(
         windows.get_primary().unwrap()
     };
     return if let Some(screen_pos) = wnd.cursor_position() {
-        let virtual_gameplay_size = Vec2::new(wnd.height() as f32, wnd.height() as f32);
-        if screen_pos.x < (wnd.width() as f32 / 2. - virtual_gameplay_size.x / 2.)
-            || screen_pos.x > (wnd.width() as f32 / 2. + virtual_gameplay_size.x / 2.)
-        {
-            false
-        } else {
-            true
-        }
+        play_field.contains_screen_pos(screen_pos)
     } else {
         false
     };
 }
 
 pub(crate) fn mouse_screen_pos_to_world_pos(
-    windows: Res<Windows>,
-    camera_query: Query<(&Camera, &GlobalTransform)>,
+    windows: &Res<Windows>,
+    camera_query: &Query<(&Camera, &GlobalTransform)>,
 ) -> Vec2 {
     let (camera, camera_transform) = camera_query.single();
     let mut mouse_pos: Vec2 = Default::default();
@@ -5,6 +5,11 @@ mod player;
 mod sound;
 mod ui;
 
+use crate::enemy::enemy_archetypes::EnemyArchetypeList;
+use crate::enemy::enemy_spawn_sides::SpawnSideWeights;
+use crate::enemy::enemy_wave_script::WaveScript;
+use crate::player::planets::Scenario;
+use crate::player::player_missiles::cluster_patterns::ClusterTierList;
 use crate::enemy::EnemyPlugin;
 use crate::game_systems::*;
 use crate::player::*;
@@ -96,7 +101,12 @@ pub(crate) struct GameSettings {
     sound_level: (f64, f64, f64),
     bg_sound_level: (f64, f64, f64),
     effects_sound_level: (f64, f64, f64),
-
+    // typed on the main menu. A number is used as is, anything else is hashed, empty picks a random seed
+    seed_text: String,
+    is_daily_seed: bool,
+    scenario: Scenario,
+    // the run is lost as soon as any planet falls instead of once they all have
+    is_lose_on_any_planet: bool,
 }
 
 impl Default for GameSettings {
@@ -107,7 +117,10 @@ impl Default for GameSettings {
             sound_level: (0.0, 0.5, 1.0),
             bg_sound_level: (0.0, 0.15, 1.0),
             effects_sound_level: (0.0, 0.5, 1.0),
-
+            seed_text: String::new(),
+            is_daily_seed: false,
+            scenario: Scenario::default(),
+            is_lose_on_any_planet: false,
         }
     }
 }
@@ -121,6 +134,32 @@ impl GameSettings {
         self.is_bg_sound_on = !self.is_bg_sound_on;
         sound_settings_event.send(SoundSettingsEvents::BGToggle(self.is_bg_sound_on));
     }
+    fn toggle_daily_seed(&mut self) {
+        self.is_daily_seed = !self.is_daily_seed;
+    }
+    fn next_scenario(&mut self) {
+        self.scenario = self.scenario.next();
+    }
+    fn toggle_lose_on_any_planet(&mut self) {
+        self.is_lose_on_any_planet = !self.is_lose_on_any_planet;
+    }
+    /// The seed the next run should use based on the main menu settings
+    fn run_seed(&self) -> u64 {
+        if self.is_daily_seed {
+            return daily_seed();
+        }
+        let seed_text = self.seed_text.trim();
+        if seed_text.is_empty() {
+            return rand::random();
+        }
+        if let Ok(seed) = seed_text.parse::<u64>() {
+            return seed;
+        }
+        // fnv-1a so the same word gives the same seed on every build and platform
+        seed_text.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        })
+    }
 }
 
 pub(crate) struct RestartGameEvent;
@@ -152,6 +191,8 @@ struct AssetHolder {
     pub player_missile_explosion_medium: Handle<Image>,
     #[asset(path = "player_missile_target.png")]
     pub player_missile_target: Handle<Image>,
+    #[asset(path = "player_missile_large.png")]
+    pub player_missile_large: Handle<Image>,
 
     #[asset(path = "space_commander_logo.png")]
     pub logo: Handle<Image>,
@@ -170,6 +211,15 @@ struct AssetHolder {
     #[asset(path = "warning_sprite.png")]
     pub warning: Handle<Image>,
 
+    #[asset(path = "data/default.archetypes.ron")]
+    pub enemy_archetypes: Handle<EnemyArchetypeList>,
+    #[asset(path = "data/default.waves.ron")]
+    pub wave_script: Handle<WaveScript>,
+    #[asset(path = "data/default.spawns.ron")]
+    pub spawn_side_weights: Handle<SpawnSideWeights>,
+    #[asset(path = "data/default.clusters.ron")]
+    pub cluster_tiers: Handle<ClusterTierList>,
+
     #[asset(path = "OpenSans-ExtraBold.ttf")]
     pub font: Handle<Font>,
     /*
@@ -244,3 +294,33 @@ fn turn_off_physics(mut physics: ResMut<RapierConfiguration>) {
 fn send_restart_game_event(mut event_writer: EventWriter<RestartGameEvent>) {
     event_writer.send(RestartGameEvent);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings_with_seed(seed_text: &str) -> GameSettings {
+        GameSettings {
+            seed_text: seed_text.to_string(),
+            ..default()
+        }
+    }
+
+    #[test]
+    fn run_seed_is_stable_for_the_same_text() {
+        let seed = settings_with_seed("andromeda").run_seed();
+        assert_eq!(seed, settings_with_seed("andromeda").run_seed());
+        assert_eq!(seed, settings_with_seed("  andromeda ").run_seed());
+        assert_ne!(seed, settings_with_seed("andromeda2").run_seed());
+    }
+
+    #[test]
+    fn run_seed_hashes_text_with_fnv_1a() {
+        assert_eq!(settings_with_seed("a").run_seed(), 0xaf63dc4c8601ec8c);
+    }
+
+    #[test]
+    fn run_seed_uses_numbers_as_is() {
+        assert_eq!(settings_with_seed("12345").run_seed(), 12345);
+    }
+}
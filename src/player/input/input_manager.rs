@@ -1,8 +1,10 @@
 ﻿use crate::GameState;
 use bevy::app::AppExit;
 use bevy::input::keyboard::KeyboardInput;
+use bevy::input::mouse::MouseWheel;
 use std::process::exit;
 
+use crate::game_systems::PlayField;
 use crate::helpers::{mouse_screen_pos_to_world_pos, mouse_virtual_play_field_check};
 use bevy::prelude::*;
 use iyes_loopless::prelude::*;
@@ -12,8 +14,28 @@ pub(crate) struct PlayerInputPlugin;
 //#[derive(Default)]
 pub(crate) enum PlayerInputEvents {
     FireMissile(Vec2),
+    // shift click. Locks onto the nearest visible enemy if the homing upgrade is bought
+    FireHomingMissile(Vec2),
+    // a missile that waits at the target for the fuse delay before exploding
+    FireFusedMissile(Vec2),
     Scan,
+    // middle click or Q. A narrow scan aimed at the mouse
+    ConeScan(Vec2),
+    // B. Drops a sensor buoy at the mouse
+    PlaceBuoy(Vec2),
     Shield(bool),
+    // F. Swaps between the arc and the full bubble shield
+    ToggleShieldMode,
+    SelectWeapon(WeaponSelection),
+}
+
+#[derive(Clone, Copy)]
+pub(crate) enum WeaponSelection {
+    // number keys, starting at 0 for the 1 key
+    Slot(usize),
+    // scroll wheel
+    Next,
+    Previous,
 }
 
 #[derive(Default)]
@@ -76,12 +98,22 @@ pub(crate) fn player_input(
     mut upgrade_menu_event: EventWriter<UpgradeMenuEvent>,
     windows: Res<Windows>,
     camera_query: Query<(&Camera, &GlobalTransform)>,
+    play_field: Res<PlayField>,
+    mut mouse_wheel_events: EventReader<MouseWheel>,
     mut commands: Commands,
 ) {
     if mouse_input.just_pressed(MouseButton::Left) {
-        if mouse_virtual_play_field_check(&windows, &camera_query) {
-            let mouse_world_pos = mouse_screen_pos_to_world_pos(windows, camera_query);
-            input_event_writer.send(PlayerInputEvents::FireMissile(mouse_world_pos));
+        if mouse_virtual_play_field_check(&windows, &camera_query, &play_field) {
+            let mouse_world_pos = mouse_screen_pos_to_world_pos(&windows, &camera_query);
+            if keyboard_input.pressed(KeyCode::LShift) || keyboard_input.pressed(KeyCode::RShift) {
+                input_event_writer.send(PlayerInputEvents::FireHomingMissile(mouse_world_pos));
+            } else if keyboard_input.pressed(KeyCode::LControl)
+                || keyboard_input.pressed(KeyCode::RControl)
+            {
+                input_event_writer.send(PlayerInputEvents::FireFusedMissile(mouse_world_pos));
+            } else {
+                input_event_writer.send(PlayerInputEvents::FireMissile(mouse_world_pos));
+            }
         }
     }
 
@@ -89,6 +121,34 @@ pub(crate) fn player_input(
         input_event_writer.send(PlayerInputEvents::Scan);
     }
 
+    if keyboard_input.just_pressed(KeyCode::B) {
+        if mouse_virtual_play_field_check(&windows, &camera_query, &play_field) {
+            let mouse_world_pos = mouse_screen_pos_to_world_pos(&windows, &camera_query);
+            input_event_writer.send(PlayerInputEvents::PlaceBuoy(mouse_world_pos));
+        }
+    }
+
+    if mouse_input.just_pressed(MouseButton::Middle) || keyboard_input.just_pressed(KeyCode::Q) {
+        if mouse_virtual_play_field_check(&windows, &camera_query, &play_field) {
+            let mouse_world_pos = mouse_screen_pos_to_world_pos(&windows, &camera_query);
+            input_event_writer.send(PlayerInputEvents::ConeScan(mouse_world_pos));
+        }
+    }
+
+    let weapon_keys = [KeyCode::Key1, KeyCode::Key2, KeyCode::Key3, KeyCode::Key4];
+    for (slot, key) in weapon_keys.iter().enumerate() {
+        if keyboard_input.just_pressed(*key) {
+            input_event_writer.send(PlayerInputEvents::SelectWeapon(WeaponSelection::Slot(slot)));
+        }
+    }
+    for event in mouse_wheel_events.iter() {
+        if event.y > 0. {
+            input_event_writer.send(PlayerInputEvents::SelectWeapon(WeaponSelection::Previous));
+        } else if event.y < 0. {
+            input_event_writer.send(PlayerInputEvents::SelectWeapon(WeaponSelection::Next));
+        }
+    }
+
     if keyboard_input.just_pressed(KeyCode::Space) {
         input_event_writer.send(PlayerInputEvents::Shield(true));
     }
@@ -96,6 +156,10 @@ pub(crate) fn player_input(
         input_event_writer.send(PlayerInputEvents::Shield(false));
     }
 
+    if keyboard_input.just_pressed(KeyCode::F) {
+        input_event_writer.send(PlayerInputEvents::ToggleShieldMode);
+    }
+
     if keyboard_input.just_pressed(KeyCode::Escape) {
         commands.insert_resource(NextState(GameState::Pause));
     }
@@ -1,14 +1,19 @@
 ﻿pub(crate) mod input;
-mod player_missiles;
-mod scanner;
-mod shield;
+pub(crate) mod planets;
+pub(crate) mod player_missiles;
+pub(crate) mod satellites;
+pub(crate) mod scanner;
+pub(crate) mod shield;
 
 use crate::player::input::input_manager::*;
+use crate::player::planets::{FallenPlanet, Planet, PlanetPlugin, Planets};
 use crate::player::player_missiles::player_missile_core::*;
 use crate::player::scanner::scanner_core::*;
-use crate::{AssetHolder, GameState, RestartGameEvent};
+use crate::{AssetHolder, GameSettings, GameState, RestartGameEvent};
 
+use crate::enemy::enemy_movement::EnemyMovement;
 use crate::enemy::{Destroyed, Enemy};
+use crate::player::satellites::satellite_core::SatellitePlugin;
 use crate::player::shield::shield_core::ShieldPlugin;
 use crate::sound::SoundEffectEvents;
 
@@ -21,7 +26,7 @@ pub struct PlayerPlugin;
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugin(ScannerPlugin)
-            .add_enter_system(GameState::GameSetupOnce, setup_player)
+            .add_plugin(PlanetPlugin)
             .add_system_set(
                 ConditionSet::new()
                     .with_system(handle_restart_game_events.run_on_event::<RestartGameEvent>())
@@ -43,15 +48,19 @@ impl Plugin for PlayerPlugin {
             )
             .add_plugin(PlayerInputPlugin)
             .add_plugin(ShieldPlugin)
+            .add_plugin(SatellitePlugin)
             .add_plugin(PlayerMissilePlugin);
     }
 }
 
 pub struct PlayerStats {
     pub(crate) is_auto_scan: bool,
+    pub(crate) is_missile_eta_display: bool,
+    pub(crate) fuse_delay_info: (f32, f32, f32), // (seconds a fused missile waits at its target, the min delay you can set, the max delay you can set)
     pub(crate) auto_scan_info: (f32, f32, f32, f32), // (current time till next scan, the target time till next scan, the min time you can set it to, the max time you can set it to)
 
-    pub(crate) current_health: u32,
+    // one entry per planet, a planet at 0 has fallen
+    pub(crate) planet_health: Vec<u32>,
     pub(crate) max_health: u32,
     pub(crate) health_recharge_time: (f32, f32, f32),
     pub(crate) time_till_next_health: f32,
@@ -65,6 +74,17 @@ pub struct PlayerStats {
 
     pub(crate) missile_speed: (f32, f32, f32),
     pub(crate) missile_energy_cost: u32,
+    pub(crate) missile_damage: f32,
+    pub(crate) homing_missile_energy_cost: u32,
+    pub(crate) mine_energy_cost: u32,
+    pub(crate) emp_energy_cost: u32,
+    pub(crate) railgun_energy_cost: u32,
+    // radians per second a homing missile can turn
+    pub(crate) homing_turn_rate: f32,
+    // how many blasts a single kill can set off in a row
+    pub(crate) chain_reaction_max_depth: u32,
+    // score for each link, multiplied by how deep the link is
+    pub(crate) chain_combo_score: u32,
 
     pub(crate) current_points: u32,
     pub(crate) locked_score: u32,
@@ -72,11 +92,51 @@ pub struct PlayerStats {
 
     pub(crate) scan_speed: (f32, f32, f32),
     pub(crate) scan_energy_cost: u32,
+    pub(crate) ghost_trail_length: (u32, u32, u32), // (past sightings kept per enemy, max, step)
+    pub(crate) sensor_accuracy: (f32, f32, f32), // (ghost position noise per unit of distance from the scan origin, the lowest it can go, how much each upgrade removes)
+    pub(crate) cone_scan_energy_cost: u32,
+    // half the width of the cone in radians
+    pub(crate) cone_scan_half_angle: f32,
+    // how much faster and farther the cone travels than the full scan
+    pub(crate) cone_scan_speed_multiplier: f32,
+    pub(crate) cone_scan_range_multiplier: f32,
+    pub(crate) buoy_limit: u32,
+    pub(crate) buoy_energy_cost: u32,
+    // seconds between each energy a live buoy drains
+    pub(crate) buoy_drain_rate: f32,
+    // seconds between each buoy scan
+    pub(crate) buoy_pulse_rate: f32,
+    pub(crate) buoy_scan_size: f32,
+    pub(crate) trajectory_assist_level: (u32, u32), // (current tier, max tier). 1 draws the trajectory line, 2 adds the intercept point
 
     pub(crate) shield_energy_cost: u32,
     pub(crate) shield_cost_rate: f32,
-
-    pub(crate) enemy_kill_score: u32,
+    pub(crate) shield_damage: f32,
+    // radians either side of the mouse the arc shield covers
+    pub(crate) arc_shield_half_angle: f32,
+    // the arc shield lasts this many times longer per energy than the bubble
+    pub(crate) arc_shield_cost_rate_multiplier: f32,
+    // each enemy the shield blocks costs one integrity, at zero the shield overloads
+    pub(crate) shield_max_integrity: u32,
+    pub(crate) current_shield_integrity: u32,
+    // seconds between each integrity regained while the shield is down
+    pub(crate) shield_integrity_recharge_rate: f32,
+    // seconds the shield can't be raised after it overloads
+    pub(crate) shield_overload_cooldown: f32,
+    // seconds after raising the shield that a block counts as perfectly timed
+    pub(crate) counter_pulse_window: f32,
+    pub(crate) counter_pulse_radius: f32,
+    pub(crate) counter_pulse_damage: f32,
+    pub(crate) counter_pulse_energy_refund: u32,
+
+    pub(crate) satellite_count: (u32, u32, u32), // (satellites bought and still in orbit, max, step)
+    pub(crate) satellite_orbit_radius: f32,
+    // radians per second
+    pub(crate) satellite_orbit_speed: f32,
+    pub(crate) satellite_range: f32,
+    pub(crate) satellite_fire_rate: (f32, f32, f32), // (seconds between interceptors, the lowest it can go, how much each upgrade removes)
+    pub(crate) interceptor_speed: f32,
+    pub(crate) interceptor_damage: f32,
 
     //costs for upgrades
     pub(crate) max_energy_upgrade_cost: u32,
@@ -89,10 +149,17 @@ pub struct PlayerStats {
     pub(crate) current_health_increase_cost: u32,
 
     pub(crate) scan_speed_upgrade_cost: u32,
+    pub(crate) ghost_trail_upgrade_cost: u32,
+    pub(crate) sensor_accuracy_upgrade_cost: u32,
+    pub(crate) trajectory_assist_upgrade_cost: u32,
 
     pub(crate) shield_time_upgrade_cost: u32,
+    pub(crate) shield_integrity_upgrade_cost: u32,
+
+    pub(crate) satellite_upgrade_cost: u32,
+    pub(crate) satellite_fire_rate_upgrade_cost: u32,
 
-    pub(crate) is_cluster_missile_upgrade: bool,
+    pub(crate) cluster_missile_level: (u32, u32), // (current tier, max tier). the tiers themselves are in assets/data/default.clusters.ron and set the max
     pub(crate) cluster_missile_upgrade_cost: u32,
 
     pub(crate) is_energy_vampire_upgrade: bool,
@@ -101,9 +168,18 @@ pub struct PlayerStats {
     pub(crate) is_dying_scanners_upgrade: bool,
     pub(crate) dying_scanners_upgrade_cost: u32,
 
-    pub(crate) is_larger_missiles_upgrade: bool,
+    pub(crate) is_counter_pulse_upgrade: bool,
+    pub(crate) counter_pulse_upgrade_cost: u32,
+
+    pub(crate) larger_missiles_level: (u32, u32), // (current tier, max tier). 0 small, 1 medium, 2 large explosions
     pub(crate) larger_missiles_upgrade_cost: u32,
 
+    pub(crate) is_homing_missile_upgrade: bool,
+    pub(crate) homing_missile_upgrade_cost: u32,
+
+    pub(crate) is_chain_reaction_upgrade: bool,
+    pub(crate) chain_reaction_upgrade_cost: u32,
+
     pub(crate) all_time_score_count: u32,
 
     pub(crate) tutorial_panel: u32,
@@ -114,9 +190,11 @@ impl Default for PlayerStats {
     fn default() -> Self {
         PlayerStats {
             is_auto_scan: false,
+            is_missile_eta_display: false,
+            fuse_delay_info: (2.0, 0.5, 8.0),
             auto_scan_info: (0., 5.0, 1.0, 10.0),
 
-            current_health: 2,
+            planet_health: vec![2],
             max_health: 2,
             health_recharge_time: (30., 1., 1.),
             time_till_next_health: 0.,
@@ -130,6 +208,14 @@ impl Default for PlayerStats {
 
             missile_speed: (100., 500., 25.),
             missile_energy_cost: 1,
+            missile_damage: 1.,
+            homing_missile_energy_cost: 2,
+            mine_energy_cost: 2,
+            emp_energy_cost: 3,
+            railgun_energy_cost: 4,
+            homing_turn_rate: 3.0,
+            chain_reaction_max_depth: 3,
+            chain_combo_score: 5,
 
             current_points: 0,
             locked_score: 0,
@@ -137,10 +223,39 @@ impl Default for PlayerStats {
 
             scan_speed: (50.0, 200., 25.),
             scan_energy_cost: 2,
+            ghost_trail_length: (0, 6, 2),
+            sensor_accuracy: (0.08, 0.0, 0.02),
+            cone_scan_energy_cost: 1,
+            cone_scan_half_angle: 0.3,
+            cone_scan_speed_multiplier: 2.5,
+            cone_scan_range_multiplier: 1.5,
+            buoy_limit: 3,
+            buoy_energy_cost: 2,
+            buoy_drain_rate: 8.0,
+            buoy_pulse_rate: 3.0,
+            buoy_scan_size: 120.,
+            trajectory_assist_level: (0, 2),
 
             shield_energy_cost: 1,
             shield_cost_rate: 1.0,
-            enemy_kill_score: 5,
+            shield_damage: 1.,
+            arc_shield_half_angle: 0.785,
+            arc_shield_cost_rate_multiplier: 4.0,
+            shield_max_integrity: 5,
+            current_shield_integrity: 5,
+            shield_integrity_recharge_rate: 2.0,
+            shield_overload_cooldown: 6.0,
+            counter_pulse_window: 0.2,
+            counter_pulse_radius: 140.,
+            counter_pulse_damage: 1.,
+            counter_pulse_energy_refund: 3,
+            satellite_count: (0, 4, 1),
+            satellite_orbit_radius: 90.,
+            satellite_orbit_speed: 0.6,
+            satellite_range: 120.,
+            satellite_fire_rate: (3.0, 1.0, 0.5),
+            interceptor_speed: 220.,
+            interceptor_damage: 1.,
 
             //costs for upgrades
             max_energy_upgrade_cost: 15,
@@ -153,10 +268,17 @@ impl Default for PlayerStats {
             current_health_increase_cost: 15,
 
             scan_speed_upgrade_cost: 10,
+            ghost_trail_upgrade_cost: 30,
+            sensor_accuracy_upgrade_cost: 25,
+            trajectory_assist_upgrade_cost: 60,
 
             shield_time_upgrade_cost: 10,
+            shield_integrity_upgrade_cost: 20,
 
-            is_cluster_missile_upgrade: false,
+            satellite_upgrade_cost: 60,
+            satellite_fire_rate_upgrade_cost: 30,
+
+            cluster_missile_level: (0, 0),
             cluster_missile_upgrade_cost: 200,
 
             is_energy_vampire_upgrade: false,
@@ -165,9 +287,18 @@ impl Default for PlayerStats {
             is_dying_scanners_upgrade: false,
             dying_scanners_upgrade_cost: 200,
 
-            is_larger_missiles_upgrade: false,
+            is_counter_pulse_upgrade: false,
+            counter_pulse_upgrade_cost: 250,
+
+            larger_missiles_level: (0, 2),
             larger_missiles_upgrade_cost: 200,
 
+            is_homing_missile_upgrade: false,
+            homing_missile_upgrade_cost: 200,
+
+            is_chain_reaction_upgrade: false,
+            chain_reaction_upgrade_cost: 250,
+
             all_time_score_count: 0,
 
             tutorial_panel: 0,
@@ -182,6 +313,10 @@ impl PlayerStats {
         self.auto_scan_info.0 = 0.;
     }
 
+    pub(crate) fn toggle_missile_eta_display(&mut self) {
+        self.is_missile_eta_display = !self.is_missile_eta_display;
+    }
+
     pub(crate) fn auto_scan_tick(
         &mut self,
         time: Res<Time>,
@@ -226,15 +361,64 @@ impl PlayerStats {
         self.current_energy -= self.missile_energy_cost;
     }
 
+    pub(crate) fn homing_missile_fired(&mut self) {
+        self.current_energy -= self.homing_missile_energy_cost;
+    }
+
+    pub(crate) fn weapon_fired(&mut self, energy_cost: u32) {
+        self.current_energy -= energy_cost;
+    }
+
     pub(crate) fn scanner_fired(&mut self) {
         self.current_energy -= self.scan_energy_cost;
         self.auto_scan_info.0 = 0.;
     }
 
+    pub(crate) fn cone_scanner_fired(&mut self) {
+        self.current_energy -= self.cone_scan_energy_cost;
+    }
+
+    pub(crate) fn buoy_placed(&mut self) {
+        self.current_energy -= self.buoy_energy_cost;
+    }
+
+    pub(crate) fn buoy_drained(&mut self) {
+        self.current_energy -= 1;
+    }
+
     pub(crate) fn shield_cost(&mut self) {
         self.current_energy -= self.shield_energy_cost;
     }
 
+    /// Removes one integrity for a blocked enemy. Returns true if that overloaded the shield
+    pub(crate) fn shield_blocked(&mut self) -> bool {
+        self.current_shield_integrity = self.current_shield_integrity.saturating_sub(1);
+        self.current_shield_integrity == 0
+    }
+
+    pub(crate) fn recharge_shield_integrity(&mut self) {
+        if self.current_shield_integrity < self.shield_max_integrity {
+            self.current_shield_integrity += 1;
+        }
+    }
+
+    pub(crate) fn satellite_destroyed(&mut self) {
+        self.satellite_count.0 = self.satellite_count.0.saturating_sub(1);
+    }
+
+    pub(crate) fn counter_pulse_refund(&mut self) {
+        self.current_energy = (self.current_energy + self.counter_pulse_energy_refund).min(self.max_energy);
+    }
+
+    /// Seconds between each shield cost for the arc or the full bubble
+    pub(crate) fn shield_cost_rate_for(&self, is_arc: bool) -> f32 {
+        if is_arc {
+            self.shield_cost_rate * self.arc_shield_cost_rate_multiplier
+        } else {
+            self.shield_cost_rate
+        }
+    }
+
     //upgrades
 
     //ENERGY
@@ -280,7 +464,9 @@ impl PlayerStats {
     pub(crate) fn upgrade_max_health(&mut self) -> bool {
         if self.check_if_enough_score(self.max_health_upgrade_cost) {
             self.max_health += 1;
-            self.heal();
+            for health in self.planet_health.iter_mut().filter(|health| **health > 0) {
+                *health += 1;
+            }
             self.increase_all_time_score_count(self.max_health_upgrade_cost);
             self.remove_score(self.max_health_upgrade_cost);
             return true;
@@ -290,9 +476,9 @@ impl PlayerStats {
 
     pub(crate) fn plus_current_health(&mut self) -> bool {
         if self.check_if_enough_score(self.current_health_increase_cost)
-            && self.current_health < self.max_health
+            && !self.check_energy_full_health()
         {
-            self.current_health += 1;
+            self.heal();
             self.increase_all_time_score_count(self.current_health_increase_cost);
             self.remove_score(self.current_health_increase_cost);
             return true;
@@ -300,21 +486,36 @@ impl PlayerStats {
         return false;
     }
 
+    /// Heals the most damaged planet that hasn't fallen
     pub(crate) fn heal(&mut self) -> bool {
-        if self.current_health < self.max_health {
-            self.current_health += 1;
+        let max_health = self.max_health;
+        if let Some(health) = self
+            .planet_health
+            .iter_mut()
+            .filter(|health| **health > 0 && **health < max_health)
+            .min_by_key(|health| **health)
+        {
+            *health += 1;
             return true;
         }
         return false;
     }
 
     pub(crate) fn check_energy_full_health(&mut self) -> bool {
-        if self.current_health < self.max_health {
+        if self
+            .planet_health
+            .iter()
+            .any(|health| *health > 0 && *health < self.max_health)
+        {
             return false;
         }
         return true;
     }
 
+    pub(crate) fn reset_planet_health(&mut self, planet_count: usize) {
+        self.planet_health = vec![self.max_health; planet_count];
+    }
+
     //SCAN
     pub(crate) fn upgrade_scan_speed(&mut self) -> bool {
         if self.check_if_enough_score(self.scan_speed_upgrade_cost)
@@ -334,6 +535,67 @@ impl PlayerStats {
         return false;
     }
 
+    pub(crate) fn upgrade_ghost_trail(&mut self) -> bool {
+        if self.check_if_enough_score(self.ghost_trail_upgrade_cost)
+            && self.ghost_trail_length.0 < self.ghost_trail_length.1
+        {
+            self.ghost_trail_length.0 += self.ghost_trail_length.2;
+            self.increase_all_time_score_count(self.ghost_trail_upgrade_cost);
+            self.remove_score(self.ghost_trail_upgrade_cost);
+            return true;
+        }
+        return false;
+    }
+
+    pub(crate) fn check_ghost_trail_maxed(&mut self) -> bool {
+        if self.ghost_trail_length.0 >= self.ghost_trail_length.1 {
+            return true;
+        }
+        return false;
+    }
+
+    pub(crate) fn upgrade_sensor_accuracy(&mut self) -> bool {
+        if self.check_if_enough_score(self.sensor_accuracy_upgrade_cost)
+            && !self.check_sensor_accuracy_maxed()
+        {
+            self.sensor_accuracy.0 =
+                (self.sensor_accuracy.0 - self.sensor_accuracy.2).max(self.sensor_accuracy.1);
+            self.increase_all_time_score_count(self.sensor_accuracy_upgrade_cost);
+            self.remove_score(self.sensor_accuracy_upgrade_cost);
+            return true;
+        }
+        return false;
+    }
+
+    pub(crate) fn check_sensor_accuracy_maxed(&mut self) -> bool {
+        // small margin so float steps still count as maxed
+        if self.sensor_accuracy.0 <= self.sensor_accuracy.1 + 0.001 {
+            return true;
+        }
+        return false;
+    }
+
+    pub(crate) fn upgrade_trajectory_assist(&mut self) -> bool {
+        if self.check_if_enough_score(self.trajectory_assist_upgrade_cost)
+            && self.trajectory_assist_level.0 < self.trajectory_assist_level.1
+        {
+            self.trajectory_assist_level.0 += 1;
+            self.increase_all_time_score_count(self.trajectory_assist_upgrade_cost);
+            self.remove_score(self.trajectory_assist_upgrade_cost);
+            // the intercept tier costs double
+            self.trajectory_assist_upgrade_cost *= 2;
+            return true;
+        }
+        return false;
+    }
+
+    pub(crate) fn check_trajectory_assist_maxed(&mut self) -> bool {
+        if self.trajectory_assist_level.0 >= self.trajectory_assist_level.1 {
+            return true;
+        }
+        return false;
+    }
+
     //SHIELD
     pub(crate) fn upgrade_shield_time(&mut self) -> bool {
         if self.check_if_enough_score(self.shield_time_upgrade_cost) {
@@ -344,6 +606,57 @@ impl PlayerStats {
         }
         return false;
     }
+    pub(crate) fn upgrade_shield_integrity(&mut self) -> bool {
+        if self.check_if_enough_score(self.shield_integrity_upgrade_cost) {
+            self.shield_max_integrity += 1;
+            self.current_shield_integrity += 1;
+            self.increase_all_time_score_count(self.shield_integrity_upgrade_cost);
+            self.remove_score(self.shield_integrity_upgrade_cost);
+            return true;
+        }
+        return false;
+    }
+
+    //SATELLITES
+    pub(crate) fn upgrade_satellite_count(&mut self) -> bool {
+        if self.check_if_enough_score(self.satellite_upgrade_cost)
+            && !self.check_satellite_count_maxed()
+        {
+            self.satellite_count.0 += self.satellite_count.2;
+            self.increase_all_time_score_count(self.satellite_upgrade_cost);
+            self.remove_score(self.satellite_upgrade_cost);
+            return true;
+        }
+        return false;
+    }
+
+    pub(crate) fn check_satellite_count_maxed(&mut self) -> bool {
+        if self.satellite_count.0 >= self.satellite_count.1 {
+            return true;
+        }
+        return false;
+    }
+
+    pub(crate) fn upgrade_satellite_fire_rate(&mut self) -> bool {
+        if self.check_if_enough_score(self.satellite_fire_rate_upgrade_cost)
+            && !self.check_satellite_fire_rate_maxed()
+        {
+            self.satellite_fire_rate.0 = (self.satellite_fire_rate.0 - self.satellite_fire_rate.2)
+                .max(self.satellite_fire_rate.1);
+            self.increase_all_time_score_count(self.satellite_fire_rate_upgrade_cost);
+            self.remove_score(self.satellite_fire_rate_upgrade_cost);
+            return true;
+        }
+        return false;
+    }
+
+    pub(crate) fn check_satellite_fire_rate_maxed(&mut self) -> bool {
+        // small margin so float steps still count as maxed
+        if self.satellite_fire_rate.0 <= self.satellite_fire_rate.1 + 0.001 {
+            return true;
+        }
+        return false;
+    }
 
     //MISSILE
     pub(crate) fn upgrade_missile_speed(&mut self) -> bool {
@@ -369,11 +682,19 @@ impl PlayerStats {
     //MISSILE
     pub(crate) fn upgrade_cluster_missile(&mut self) -> bool {
         if self.check_if_enough_score(self.cluster_missile_upgrade_cost)
-            && self.is_cluster_missile_upgrade == false
+            && self.cluster_missile_level.0 < self.cluster_missile_level.1
         {
-            self.is_cluster_missile_upgrade = true;
+            self.cluster_missile_level.0 += 1;
             self.increase_all_time_score_count(self.cluster_missile_upgrade_cost);
             self.remove_score(self.cluster_missile_upgrade_cost);
+            self.cluster_missile_upgrade_cost += 100;
+            return true;
+        }
+        return false;
+    }
+
+    pub(crate) fn check_cluster_missile_maxed(&mut self) -> bool {
+        if self.cluster_missile_level.0 >= self.cluster_missile_level.1 {
             return true;
         }
         return false;
@@ -400,13 +721,55 @@ impl PlayerStats {
         }
         return false;
     }
+    pub(crate) fn upgrade_counter_pulse(&mut self) -> bool {
+        if self.check_if_enough_score(self.counter_pulse_upgrade_cost)
+            && self.is_counter_pulse_upgrade == false
+        {
+            self.is_counter_pulse_upgrade = true;
+            self.increase_all_time_score_count(self.counter_pulse_upgrade_cost);
+            self.remove_score(self.counter_pulse_upgrade_cost);
+            return true;
+        }
+        return false;
+    }
     pub(crate) fn upgrade_larger_missiles(&mut self) -> bool {
         if self.check_if_enough_score(self.larger_missiles_upgrade_cost)
-            && self.is_larger_missiles_upgrade == false
+            && self.larger_missiles_level.0 < self.larger_missiles_level.1
         {
-            self.is_larger_missiles_upgrade = true;
+            self.larger_missiles_level.0 += 1;
             self.increase_all_time_score_count(self.larger_missiles_upgrade_cost);
             self.remove_score(self.larger_missiles_upgrade_cost);
+            // the large tier costs more
+            self.larger_missiles_upgrade_cost += 150;
+            return true;
+        }
+        return false;
+    }
+
+    pub(crate) fn check_larger_missiles_maxed(&mut self) -> bool {
+        if self.larger_missiles_level.0 >= self.larger_missiles_level.1 {
+            return true;
+        }
+        return false;
+    }
+    pub(crate) fn upgrade_homing_missile(&mut self) -> bool {
+        if self.check_if_enough_score(self.homing_missile_upgrade_cost)
+            && self.is_homing_missile_upgrade == false
+        {
+            self.is_homing_missile_upgrade = true;
+            self.increase_all_time_score_count(self.homing_missile_upgrade_cost);
+            self.remove_score(self.homing_missile_upgrade_cost);
+            return true;
+        }
+        return false;
+    }
+    pub(crate) fn upgrade_chain_reaction(&mut self) -> bool {
+        if self.check_if_enough_score(self.chain_reaction_upgrade_cost)
+            && self.is_chain_reaction_upgrade == false
+        {
+            self.is_chain_reaction_upgrade = true;
+            self.increase_all_time_score_count(self.chain_reaction_upgrade_cost);
+            self.remove_score(self.chain_reaction_upgrade_cost);
             return true;
         }
         return false;
@@ -441,12 +804,15 @@ impl PlayerStats {
     }
 
     //health stuff
-    pub(crate) fn damage(&mut self) -> bool {
-        if self.current_health as i32 - 1 as i32 <= 0 {
-            self.current_health = 0;
-            return true;
-        } else {
-            self.current_health -= 1;
+    /// Returns true if this damage made the planet fall
+    pub(crate) fn damage(&mut self, planet_index: usize) -> bool {
+        if let Some(health) = self.planet_health.get_mut(planet_index) {
+            if *health as i32 - 1 as i32 <= 0 {
+                *health = 0;
+                return true;
+            } else {
+                *health -= 1;
+            }
         }
         return false;
     }
@@ -471,10 +837,6 @@ impl PlayerStats {
     }
 }
 
-fn setup_player(mut commands: Commands, sprites: Res<AssetHolder>) {
-    commands.spawn_bundle(PlayerBundle::new(sprites));
-}
-
 pub struct ScoreEvent(pub(crate) u32);
 
 #[derive(Component)]
@@ -493,16 +855,12 @@ pub struct PlayerBundle {
 }
 
 impl PlayerBundle {
-    pub(crate) fn new(sprites: Res<AssetHolder>) -> PlayerBundle {
+    pub(crate) fn new(sprites: &Res<AssetHolder>, location: Vec2) -> PlayerBundle {
         PlayerBundle {
             sprite_bundle: SpriteBundle {
                 sprite: Default::default(),
                 transform: Transform {
-                    translation: Vec3 {
-                        x: 0.0,
-                        y: 0.0,
-                        z: 50.0,
-                    },
+                    translation: location.extend(50.0),
                     rotation: Default::default(),
                     scale: Vec3 {
                         x: 3.0,
@@ -537,7 +895,7 @@ pub fn handle_player_energy_and_health_recharge(
     time: Res<Time>,
 ) {
     //health recharge
-    if player_stats.current_health < player_stats.max_health {
+    if !player_stats.check_energy_full_health() {
         player_stats.time_till_next_health += time.delta_seconds();
         if player_stats.time_till_next_health >= player_stats.health_recharge_time.0 {
             player_stats.time_till_next_health = 0.;
@@ -556,20 +914,48 @@ pub fn handle_player_energy_and_health_recharge(
 }
 
 pub(crate) fn handle_player_planet_collisions(
-    mut missiles: Query<(&CollidingEntities), With<Player>>,
+    sprites: Res<AssetHolder>,
+    mut missiles: Query<
+        (Entity, &CollidingEntities, &Planet, &mut Handle<Image>),
+        (With<Player>, Without<FallenPlanet>),
+    >,
     mut enemy_entities: Query<&Enemy>,
+    mut enemy_movements: Query<&mut EnemyMovement>,
     mut player_stats: ResMut<PlayerStats>,
+    mut planets: ResMut<Planets>,
+    game_settings: Res<GameSettings>,
     mut commands: Commands,
     mut sound_effect_writer: EventWriter<SoundEffectEvents>,
 ) {
-    for entities in missiles.iter_mut() {
+    for (entity, entities, planet, mut texture) in missiles.iter_mut() {
         for collision in entities.iter() {
             if let Ok(_enemy) = enemy_entities.get(collision) {
-                commands.entity(_enemy.scan_ghost).despawn();
+                commands.entity(_enemy.scan_ghost).despawn_recursive();
                 commands.entity(collision).despawn();
                 sound_effect_writer.send(SoundEffectEvents::PlanetDamaged);
-                if player_stats.damage() {
-                    commands.insert_resource(NextState(GameState::Lose));
+                if player_stats.damage(planet.index) {
+                    // a fallen planet stops taking hits and enemies stop picking it
+                    *texture = sprites.player_planet_destroyed.clone();
+                    commands
+                        .entity(entity)
+                        .insert(FallenPlanet)
+                        .remove::<Collider>();
+                    if let Some(is_fallen) = planets.is_fallen.get_mut(planet.index) {
+                        *is_fallen = true;
+                    }
+                    if game_settings.is_lose_on_any_planet || planets.all_fallen() {
+                        commands.insert_resource(NextState(GameState::Lose));
+                    } else {
+                        // anything still headed for the fallen planet moves on to the closest one left
+                        let fallen_location = planets.locations[planet.index];
+                        let new_target = planets.nearest(fallen_location);
+                        for mut movement in enemy_movements.iter_mut() {
+                            if movement.target == fallen_location {
+                                movement.target = new_target;
+                            }
+                        }
+                    }
+                    break;
                 }
             }
         }
@@ -0,0 +1,176 @@
+use crate::player::{PlayerBundle, PlayerStats};
+use crate::{AssetHolder, GameSettings, GameState, RestartGameEvent};
+use bevy::prelude::*;
+use iyes_loopless::prelude::*;
+use rand::prelude::*;
+use std::f32::consts::TAU;
+
+pub(crate) struct PlanetPlugin;
+
+impl Plugin for PlanetPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Planets>();
+        // the scenario is picked on the main menu so the planets follow it there
+        app.add_system_set(
+            ConditionSet::new()
+                .run_in_state(GameState::MainMenu)
+                .with_system(sync_planets)
+                .into(),
+        );
+        app.add_system_set(
+            ConditionSet::new()
+                .with_system(handle_restart_game_events.run_on_event::<RestartGameEvent>())
+                .into(),
+        );
+    }
+}
+
+/// How many planets there are to defend and where they sit
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum Scenario {
+    Single,
+    Twin,
+    Trio,
+}
+
+impl Default for Scenario {
+    fn default() -> Self {
+        Scenario::Single
+    }
+}
+
+impl Scenario {
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            Scenario::Single => "1 PLANET",
+            Scenario::Twin => "2 PLANETS",
+            Scenario::Trio => "3 PLANETS",
+        }
+    }
+
+    /// Cycles through the scenarios for the main menu button
+    pub(crate) fn next(&self) -> Scenario {
+        match self {
+            Scenario::Single => Scenario::Twin,
+            Scenario::Twin => Scenario::Trio,
+            Scenario::Trio => Scenario::Single,
+        }
+    }
+
+    pub(crate) fn planet_locations(&self) -> Vec<Vec2> {
+        match self {
+            Scenario::Single => vec![Vec2::ZERO],
+            Scenario::Twin => vec![Vec2::new(-110., 0.), Vec2::new(110., 0.)],
+            Scenario::Trio => (0..3)
+                .map(|index| {
+                    // starts straight up so the three sit in a triangle
+                    let angle = TAU / 4. + TAU / 3. * index as f32;
+                    Vec2::new(angle.cos(), angle.sin()) * 130.
+                })
+                .collect(),
+        }
+    }
+}
+
+/// One of the planets. index matches its health in PlayerStats::planet_health
+#[derive(Component)]
+pub(crate) struct Planet {
+    pub(crate) index: usize,
+}
+
+/// Marks a planet that lost all its health
+#[derive(Component)]
+pub(crate) struct FallenPlanet;
+
+/// Where the planets are, for everything that used to act from the origin
+#[derive(Default)]
+pub(crate) struct Planets {
+    pub(crate) locations: Vec<Vec2>,
+    pub(crate) is_fallen: Vec<bool>,
+}
+
+impl Planets {
+    pub(crate) fn living(&self) -> impl Iterator<Item = Vec2> + '_ {
+        self.locations
+            .iter()
+            .zip(self.is_fallen.iter())
+            .filter(|(_, is_fallen)| !**is_fallen)
+            .map(|(location, _)| *location)
+    }
+
+    /// The living planet closest to point. Missiles and cone scans launch from here
+    pub(crate) fn nearest(&self, point: Vec2) -> Vec2 {
+        self.living()
+            .min_by(|a, b| a.distance_squared(point).total_cmp(&b.distance_squared(point)))
+            .unwrap_or(Vec2::ZERO)
+    }
+
+    /// The first living planet. The shield and satellites stay with it
+    pub(crate) fn home(&self) -> Vec2 {
+        self.living().next().unwrap_or(Vec2::ZERO)
+    }
+
+    /// A living planet for a new enemy to fly at. With one planet left the rng isn't touched so
+    /// seeds play the same as they did before there were multiple planets
+    pub(crate) fn pick_target(&self, rng: &mut impl Rng) -> Vec2 {
+        let living: Vec<Vec2> = self.living().collect();
+        match living.len() {
+            0 => Vec2::ZERO,
+            1 => living[0],
+            count => living[rng.gen_range(0..count)],
+        }
+    }
+
+    pub(crate) fn all_fallen(&self) -> bool {
+        self.is_fallen.iter().all(|is_fallen| *is_fallen)
+    }
+}
+
+/// Spawns the planets for the chosen scenario, replacing the old ones if it changed
+fn sync_planets(
+    sprites: Res<AssetHolder>,
+    game_settings: Res<GameSettings>,
+    planet_query: Query<(Entity, &Transform), With<Planet>>,
+    mut planets: ResMut<Planets>,
+    mut player_stats: ResMut<PlayerStats>,
+    mut commands: Commands,
+) {
+    let locations = game_settings.scenario.planet_locations();
+    let current: Vec<Vec2> = planet_query
+        .iter()
+        .map(|(_, transform)| transform.translation.truncate())
+        .collect();
+    let is_synced = current.len() == locations.len()
+        && locations.iter().all(|location| current.contains(location));
+
+    if !is_synced {
+        for (entity, _) in planet_query.iter() {
+            commands.entity(entity).despawn();
+        }
+        for (index, location) in locations.iter().enumerate() {
+            commands
+                .spawn_bundle(PlayerBundle::new(&sprites, *location))
+                .insert(Planet { index });
+        }
+    }
+    if !is_synced || planets.locations != locations {
+        planets.is_fallen = vec![false; locations.len()];
+        planets.locations = locations.clone();
+    }
+    // the stats are replaced on restart so this can fall out of step on its own
+    if player_stats.planet_health.len() != locations.len() {
+        player_stats.reset_planet_health(locations.len());
+    }
+}
+
+/// Clears out the planets from the last run, sync_planets puts fresh ones back
+fn handle_restart_game_events(
+    mut commands: Commands,
+    planet_query: Query<Entity, With<Planet>>,
+    mut planets: ResMut<Planets>,
+) {
+    for entity in planet_query.iter() {
+        commands.entity(entity).despawn();
+    }
+    *planets = Planets::default();
+}
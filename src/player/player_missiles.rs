@@ -1 +1,4 @@
-﻿pub mod player_missile_core;
\ No newline at end of file
+﻿pub mod player_missile_core;
+pub(crate) mod chain_reaction;
+pub(crate) mod cluster_patterns;
+pub(crate) mod weapons;
\ No newline at end of file
@@ -0,0 +1,126 @@
+use crate::enemy::{damage_enemy, ChainLink, Enemy, EnemyHealth, EnemyKilledEvent, KillSource};
+use crate::sound::SoundEffectEvents;
+use crate::{AssetHolder, GameState, PlayerStats, RestartGameEvent, ScoreEvent};
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use iyes_loopless::prelude::*;
+use std::time::Duration;
+
+pub(crate) struct ChainReactionPlugin;
+
+impl Plugin for ChainReactionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(
+            ConditionSet::new()
+                .run_in_state(GameState::Playing)
+                .label("chain_reaction")
+                .with_system(handle_enemy_killed_events.run_on_event::<EnemyKilledEvent>())
+                .with_system(handle_chain_blast_collisions)
+                .with_system(update_chain_blasts)
+                .into(),
+        );
+        app.add_system_set(
+            ConditionSet::new()
+                .with_system(handle_restart_game_events.run_on_event::<RestartGameEvent>())
+                .into(),
+        );
+    }
+}
+
+/// A secondary explosion left where an enemy died. Enemies it kills carry the chain on one link deeper
+#[derive(Component)]
+pub(crate) struct ChainBlast {
+    depth: u32,
+    hit_entities: Vec<Entity>,
+    lifetime: Timer,
+}
+
+fn handle_enemy_killed_events(
+    sprites: Res<AssetHolder>,
+    mut enemy_killed_event_reader: EventReader<EnemyKilledEvent>,
+    player_stats: Res<PlayerStats>,
+    mut commands: Commands,
+    mut score_event_writer: EventWriter<ScoreEvent>,
+    mut sound_effect_writer: EventWriter<SoundEffectEvents>,
+) {
+    for event in enemy_killed_event_reader.iter() {
+        // each link deeper in the chain is worth more
+        if event.chain_depth > 0 {
+            score_event_writer.send(ScoreEvent(
+                player_stats.chain_combo_score * event.chain_depth,
+            ));
+        }
+
+        let depth = event.chain_depth + 1;
+        if !player_stats.is_chain_reaction_upgrade || depth > player_stats.chain_reaction_max_depth
+        {
+            continue;
+        }
+        commands
+            .spawn_bundle(SpriteBundle {
+                transform: Transform {
+                    translation: event.location.extend(1.0),
+                    scale: Vec3::new(3.0, 3.0, 1.0),
+                    ..default()
+                },
+                texture: sprites.player_missile_explosion.clone(),
+                ..default()
+            })
+            .insert(Collider::ball(8.))
+            .insert(Sensor)
+            .insert(ActiveEvents::COLLISION_EVENTS)
+            .insert(CollidingEntities::default())
+            .insert(ChainBlast {
+                depth,
+                hit_entities: Vec::new(),
+                lifetime: Timer::new(Duration::from_secs_f32(0.2), false),
+            });
+        sound_effect_writer.send(SoundEffectEvents::MissileExplosion);
+    }
+}
+
+fn handle_chain_blast_collisions(
+    mut blasts: Query<(&CollidingEntities, &mut ChainBlast)>,
+    mut enemy_entities: Query<&mut EnemyHealth, With<Enemy>>,
+    mut commands: Commands,
+    player_stats: Res<PlayerStats>,
+) {
+    for (entities, mut blast) in blasts.iter_mut() {
+        for collision in entities.iter() {
+            if blast.hit_entities.contains(&collision) {
+                continue;
+            }
+            if let Ok(mut enemy_health) = enemy_entities.get_mut(collision) {
+                blast.hit_entities.push(collision);
+                if damage_enemy(
+                    &mut commands,
+                    collision,
+                    &mut enemy_health,
+                    player_stats.missile_damage,
+                    KillSource::Weapon,
+                ) {
+                    commands.entity(collision).insert(ChainLink(blast.depth));
+                }
+            }
+        }
+    }
+}
+
+fn update_chain_blasts(
+    mut blasts: Query<(Entity, &mut ChainBlast)>,
+    mut commands: Commands,
+    time: Res<Time>,
+) {
+    for (entity, mut blast) in blasts.iter_mut() {
+        blast.lifetime.tick(time.delta());
+        if blast.lifetime.finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn handle_restart_game_events(mut commands: Commands, blasts: Query<Entity, With<ChainBlast>>) {
+    for blast in blasts.iter() {
+        commands.entity(blast).despawn();
+    }
+}
@@ -0,0 +1,189 @@
+use crate::{AssetHolder, GameState, PlayerStats};
+use bevy::asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy::prelude::*;
+use bevy::reflect::TypeUuid;
+use bevy::utils::BoxedFuture;
+use iyes_loopless::prelude::*;
+use rand::prelude::*;
+use serde::Deserialize;
+use std::f32::consts::TAU;
+
+pub(crate) struct ClusterPatternPlugin;
+
+impl Plugin for ClusterPatternPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<ClusterTierList>()
+            .init_asset_loader::<ClusterTierLoader>()
+            .init_resource::<ClusterTiers>()
+            .add_enter_system(GameState::GameSetupOnce, setup_cluster_tiers);
+
+        // picks up edits to the cluster file while the game is running (watch_for_changes)
+        app.add_system(
+            reload_cluster_tiers
+                .run_not_in_state(GameState::AssetLoading)
+                .run_on_event::<AssetEvent<ClusterTierList>>(),
+        );
+        // the stats are replaced on restart so the max tier is kept in step with the file every frame
+        app.add_system(sync_cluster_max_level.run_not_in_state(GameState::AssetLoading));
+    }
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum ClusterPattern {
+    // sub missiles stepped out along the four axes around the target
+    Cross,
+    // evenly spaced on a circle around the target
+    Ring,
+    // random points inside a circle around the target
+    Scatter,
+    // a line through the target along the angle the missile was fired at
+    Line,
+}
+
+/// One tier of the cluster missile upgrade as written in assets/data/*.clusters.ron
+#[derive(Deserialize, Clone)]
+pub(crate) struct ClusterTier {
+    pub(crate) name: String,
+    pub(crate) pattern: ClusterPattern,
+    pub(crate) count: u32,
+    pub(crate) spread: f32,
+}
+
+impl Default for ClusterTier {
+    fn default() -> Self {
+        ClusterTier {
+            name: "Cross".to_string(),
+            pattern: ClusterPattern::Cross,
+            count: 4,
+            spread: 20.,
+        }
+    }
+}
+
+impl ClusterTier {
+    /// Where each sub missile of this tier lands for a missile fired from launch_location at target
+    pub(crate) fn targets(
+        &self,
+        launch_location: Vec2,
+        target: Vec2,
+        spread_multiplier: f32,
+        rng: &mut impl Rng,
+    ) -> Vec<Vec2> {
+        let count = self.count as usize;
+        let spread = self.spread * spread_multiplier;
+        match self.pattern {
+            ClusterPattern::Cross => {
+                let axes = [Vec2::Y, Vec2::NEG_Y, Vec2::X, Vec2::NEG_X];
+                (0..count)
+                    .map(|index| target + axes[index % 4] * spread * (index / 4 + 1) as f32)
+                    .collect()
+            }
+            ClusterPattern::Ring => (0..count)
+                .map(|index| {
+                    let angle = TAU * index as f32 / count as f32;
+                    target + Vec2::new(angle.cos(), angle.sin()) * spread
+                })
+                .collect(),
+            ClusterPattern::Scatter => (0..count)
+                .map(|_| {
+                    let angle = rng.gen_range(0.0..TAU);
+                    // sqrt keeps the points even across the circle instead of bunched in the middle
+                    let distance = spread * rng.gen::<f32>().sqrt();
+                    target + Vec2::new(angle.cos(), angle.sin()) * distance
+                })
+                .collect(),
+            ClusterPattern::Line => {
+                let direction = (target - launch_location).normalize_or_zero();
+                // alternates either side of the target: +1, -1, +2, -2...
+                (0..count)
+                    .map(|index| {
+                        let step = (index / 2 + 1) as f32;
+                        let side = if index % 2 == 0 { 1. } else { -1. };
+                        target + direction * spread * step * side
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+/// The raw cluster file
+#[derive(Deserialize, TypeUuid)]
+#[uuid = "a3e6f1d2-7c5b-4e8a-b9f0-2d4c6a8e1b37"]
+pub(crate) struct ClusterTierList {
+    pub(crate) tiers: Vec<ClusterTier>,
+}
+
+#[derive(Default)]
+pub(crate) struct ClusterTierLoader;
+
+impl AssetLoader for ClusterTierLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let tier_list = ron::de::from_bytes::<ClusterTierList>(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(tier_list));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["clusters.ron"]
+    }
+}
+
+/// The loaded cluster tiers. Tier 1 is the first entry in the file
+#[derive(Default)]
+pub(crate) struct ClusterTiers {
+    pub(crate) tiers: Vec<ClusterTier>,
+}
+
+impl ClusterTiers {
+    /// The tier for an upgrade level, clamped to the last tier in the file. None below level 1
+    pub(crate) fn get(&self, level: u32) -> Option<ClusterTier> {
+        if level == 0 {
+            return None;
+        }
+        let index = (level as usize - 1).min(self.tiers.len().saturating_sub(1));
+        Some(self.tiers.get(index).cloned().unwrap_or_default())
+    }
+}
+
+fn setup_cluster_tiers(
+    sprites: Res<AssetHolder>,
+    tier_lists: Res<Assets<ClusterTierList>>,
+    mut cluster_tiers: ResMut<ClusterTiers>,
+) {
+    if let Some(tier_list) = tier_lists.get(&sprites.cluster_tiers) {
+        cluster_tiers.tiers = tier_list.tiers.clone();
+    }
+}
+
+/// The cluster upgrade can be bought once for every tier in the file
+fn sync_cluster_max_level(cluster_tiers: Res<ClusterTiers>, mut player_stats: ResMut<PlayerStats>) {
+    let max_level = cluster_tiers.tiers.len() as u32;
+    if player_stats.cluster_missile_level.1 != max_level {
+        player_stats.cluster_missile_level.1 = max_level;
+        player_stats.cluster_missile_level.0 = player_stats.cluster_missile_level.0.min(max_level);
+    }
+}
+
+fn reload_cluster_tiers(
+    mut asset_events: EventReader<AssetEvent<ClusterTierList>>,
+    sprites: Res<AssetHolder>,
+    tier_lists: Res<Assets<ClusterTierList>>,
+    mut cluster_tiers: ResMut<ClusterTiers>,
+) {
+    for event in asset_events.iter() {
+        if let AssetEvent::Modified { handle } = event {
+            if *handle == sprites.cluster_tiers {
+                if let Some(tier_list) = tier_lists.get(handle) {
+                    cluster_tiers.tiers = tier_list.tiers.clone();
+                }
+            }
+        }
+    }
+}
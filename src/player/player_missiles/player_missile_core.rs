@@ -1,8 +1,15 @@
 ﻿use crate::player::input::input_manager::*;
 use crate::player::*;
 use crate::AssetHolder;
+use crate::game_systems::PlayField;
+use crate::player::planets::Planets;
+use crate::player::player_missiles::chain_reaction::ChainReactionPlugin;
+use crate::player::player_missiles::cluster_patterns::{ClusterPatternPlugin, ClusterTiers};
+use crate::player::player_missiles::weapons::{
+    fire_emp, fire_mine, fire_railgun, WeaponLoadout, WeaponType, WeaponsPlugin,
+};
 
-use crate::enemy::{Destroyed, Enemy};
+use crate::enemy::{damage_enemy, Enemy, EnemyHealth, KillSource, VisibilityTimer};
 use bevy::prelude::*;
 use bevy_rapier2d::parry::transformation::utils::transform;
 use bevy_rapier2d::prelude::*;
@@ -11,9 +18,8 @@ pub struct PlayerMissilePlugin;
 
 impl Plugin for PlayerMissilePlugin {
     fn build(&self, app: &mut App) {
-        app.add_event::<EnemyKilledEvent>()
-            //handles spawning missiles events and updating missiles/checking if they have arrived
-            .add_system_set(
+        //handles spawning missiles events and updating missiles/checking if they have arrived
+        app.add_system_set(
                 ConditionSet::new()
                     .run_in_state(GameState::Playing)
                     .label("missile_main")
@@ -22,6 +28,7 @@ impl Plugin for PlayerMissilePlugin {
                         handle_player_missile_spawn_events.run_on_event::<PlayerInputEvents>(),
                     )
                     .with_system(update_missiles)
+                    .with_system(update_missile_eta_text)
                     .into(),
             );
 
@@ -30,6 +37,9 @@ impl Plugin for PlayerMissilePlugin {
                 .with_system(handle_restart_game_events.run_on_event::<RestartGameEvent>())
                 .into(),
         );
+        app.add_plugin(WeaponsPlugin);
+        app.add_plugin(ChainReactionPlugin);
+        app.add_plugin(ClusterPatternPlugin);
         //handles missiles exploding
         app.add_system_set(
             ConditionSet::new()
@@ -37,17 +47,13 @@ impl Plugin for PlayerMissilePlugin {
                 .label("missile_post")
                 .before("missile_main")
                 .with_system(missile_explode)
+                .with_system(update_explosions)
                 .with_system(handle_missile_collisions)
                 .into(),
         );
     }
 }
 
-#[derive(Default)]
-pub struct EnemyKilledEvent {
-    pub(crate) location: Vec2,
-}
-
 #[derive(Default)]
 pub struct SpawnMissileEvent {
     pub(crate) target: Vec2,
@@ -61,27 +67,54 @@ pub(crate) struct PlayerMissile {
     time_since_explsion: f32,
     target_entity: Entity,
     enemy_killed: bool,
+    // enemies this missile already damaged so a lingering explosion only hits them once
+    hit_entities: Vec<Entity>,
+    // the enemy a homing missile is locked onto. target follows it until the lock is lost
+    homing_target: Option<Entity>,
+    // where the missile was aimed when fired, used again if the lock is lost
+    detonation_point: Vec2,
+    // seconds left to wait at the target before exploding
+    fuse_delay: f32,
+    // the time to target text above the reticle
+    eta_text: Entity,
 }
 
+/// The time to target text shown above a missile's reticle
+#[derive(Component)]
+pub(crate) struct MissileEtaText;
+
 impl PlayerMissile {
     pub(crate) fn spawn(
         sprites: &Res<AssetHolder>,
         player_stats: &mut ResMut<PlayerStats>,
         commands: &mut Commands,
+        launch_location: Vec2,
         mouse_pos: Vec2,
         is_cluster_missile: bool,
+        homing_target: Option<Entity>,
+        fuse_delay: f32,
         mut sound_effect_writer: &mut EventWriter<SoundEffectEvents>,
-    ) {
-        if player_stats.check_if_enough_energy(player_stats.missile_energy_cost)
-            || is_cluster_missile
-        {
+    ) -> bool {
+        let energy_cost = if homing_target.is_some() {
+            player_stats.homing_missile_energy_cost
+        } else {
+            player_stats.missile_energy_cost
+        };
+        if player_stats.check_if_enough_energy(energy_cost) || is_cluster_missile {
             if !is_cluster_missile {
-                player_stats.missile_fired();
+                if homing_target.is_some() {
+                    player_stats.homing_missile_fired();
+                } else {
+                    player_stats.missile_fired();
+                }
                 sound_effect_writer.send(SoundEffectEvents::MissileLaunched);
             }
 
             let target = mouse_pos;
-            let angle = f32::atan2(mouse_pos.y - 0., mouse_pos.x - 0.);
+            let angle = f32::atan2(
+                mouse_pos.y - launch_location.y,
+                mouse_pos.x - launch_location.x,
+            );
 
             let missile_rotation = Quat::from_rotation_z(angle);
             let rotated_velocity = missile_rotation
@@ -90,18 +123,46 @@ impl PlayerMissile {
                 y: 0.0,
                 z: 0.0,
             };
-            let is_larger = player_stats.is_larger_missiles_upgrade;
+            let eta_text = commands
+                .spawn_bundle(Text2dBundle {
+                    text: Text::from_section(
+                        "",
+                        TextStyle {
+                            font: sprites.font.clone(),
+                            font_size: 18.0,
+                            color: Color::WHITE,
+                        },
+                    )
+                        .with_alignment(TextAlignment::CENTER),
+                    // the reticle is scaled by 3 so undo that for the text
+                    transform: Transform {
+                        translation: Vec3::new(0., 8., 1.),
+                        scale: Vec3::new(1. / 3., 1. / 3., 1.),
+                        ..default()
+                    },
+                    visibility: Visibility { is_visible: false },
+                    ..default()
+                })
+                .insert(MissileEtaText)
+                .id();
             let missile_target = commands
                 .spawn_bundle(PlayerMissileTargetBundle::new(sprites, target))
+                .add_child(eta_text)
                 .id();
             commands.spawn_bundle(PlayerMissileBundle::new(
                 sprites,
+                launch_location,
                 rotated_velocity.truncate(),
                 missile_rotation,
                 target,
                 missile_target,
+                homing_target,
+                fuse_delay,
+                eta_text,
             ));
+            return true;
         }
+        false
     }
 }
 
@@ -150,20 +211,20 @@ impl PlayerMissileTargetBundle {
 impl PlayerMissileBundle {
     pub(crate) fn new(
         sprites: &Res<AssetHolder>,
+        launch_location: Vec2,
         linvel: Vec2,
         rotation: Quat,
         target: Vec2,
         target_entity: Entity,
+        homing_target: Option<Entity>,
+        fuse_delay: f32,
+        eta_text: Entity,
     ) -> PlayerMissileBundle {
         PlayerMissileBundle {
             sprite_bundle: SpriteBundle {
                 sprite: Default::default(),
                 transform: Transform {
-                    translation: Vec3 {
-                        x: 0.0,
-                        y: 0.0,
-                        z: 50.0,
-                    },
+                    translation: launch_location.extend(50.0),
                     rotation,
                     scale: Vec3 {
                         x: 3.0,
@@ -192,6 +253,11 @@ impl PlayerMissileBundle {
                 time_since_explsion: 0.0,
                 target_entity,
                 enemy_killed: false,
+                hit_entities: Vec::new(),
+                homing_target,
+                detonation_point: target,
+                fuse_delay,
+                eta_text,
             },
         }
     }
@@ -203,89 +269,200 @@ pub(crate) fn handle_player_missile_spawn_events(
     mut commands: Commands,
     mut spawn_missile_event_reader: EventReader<PlayerInputEvents>,
     mut sound_effect_writer: EventWriter<SoundEffectEvents>,
+    visible_enemies: Query<(Entity, &Transform), (With<Enemy>, With<VisibilityTimer>)>,
+    mut enemies: Query<(Entity, &Transform, &mut EnemyHealth), With<Enemy>>,
+    mut weapon_loadout: ResMut<WeaponLoadout>,
+    play_field: Res<PlayField>,
+    cluster_tiers: Res<ClusterTiers>,
+    planets: Res<Planets>,
 ) {
     for event in spawn_missile_event_reader.iter() {
         match event {
-            PlayerInputEvents::FireMissile(target) => {
-                PlayerMissile::spawn(
-                    &sprites,
-                    &mut player_stats,
-                    &mut commands,
-                    *target,
-                    false,
-                    &mut sound_effect_writer,
-                );
-                if player_stats.is_cluster_missile_upgrade {
-                    let mut cluster_dif: f32 = 20.;
-                    if player_stats.is_larger_missiles_upgrade {
-                        cluster_dif = 40.;
+            PlayerInputEvents::FireMissile(target)
+            | PlayerInputEvents::FireHomingMissile(target)
+            | PlayerInputEvents::FireFusedMissile(target) => {
+                // only the standard missile can be fused or guided, other weapons fire as normal
+                let fuse_delay = match event {
+                    PlayerInputEvents::FireFusedMissile(_) => player_stats.fuse_delay_info.0,
+                    _ => 0.,
+                };
+                // shift is ignored without the upgrade, and with nothing to lock onto this is a normal missile
+                let homing_target = match event {
+                    PlayerInputEvents::FireHomingMissile(_)
+                        if player_stats.is_homing_missile_upgrade =>
+                    {
+                        visible_enemies
+                            .iter()
+                            .min_by(|(_, a), (_, b)| {
+                                let a = a.translation.truncate().distance_squared(*target);
+                                let b = b.translation.truncate().distance_squared(*target);
+                                a.total_cmp(&b)
+                            })
+                            .map(|(enemy, _)| enemy)
                     }
-                    PlayerMissile::spawn(
-                        &sprites,
-                        &mut player_stats,
-                        &mut commands,
-                        Vec2 {
-                            x: target.x,
-                            y: target.y + cluster_dif,
-                        },
-                        true,
-                        &mut sound_effect_writer,
-                    );
-                    PlayerMissile::spawn(
-                        &sprites,
-                        &mut player_stats,
-                        &mut commands,
-                        Vec2 {
-                            x: target.x,
-                            y: target.y - cluster_dif,
-                        },
-                        true,
-                        &mut sound_effect_writer,
-                    );
-                    PlayerMissile::spawn(
+                    _ => None,
+                };
+                if !weapon_loadout.is_selected_ready() {
+                    sound_effect_writer.send(SoundEffectEvents::ErrorButton);
+                    continue;
+                }
+                let fired = match weapon_loadout.selected_weapon() {
+                    WeaponType::Missile => fire_missile(
                         &sprites,
                         &mut player_stats,
+                        &cluster_tiers,
                         &mut commands,
-                        Vec2 {
-                            x: target.x + cluster_dif,
-                            y: target.y,
-                        },
-                        true,
+                        planets.nearest(*target),
+                        *target,
+                        homing_target,
+                        fuse_delay,
                         &mut sound_effect_writer,
-                    );
-                    PlayerMissile::spawn(
-                        &sprites,
-                        &mut player_stats,
-                        &mut commands,
-                        Vec2 {
-                            x: target.x - cluster_dif,
-                            y: target.y,
-                        },
-                        true,
-                        &mut sound_effect_writer,
-                    );
+                    ),
+                    weapon_type => {
+                        let energy_cost = weapon_type.energy_cost(&player_stats);
+                        if player_stats.check_if_enough_energy(energy_cost) {
+                            player_stats.weapon_fired(energy_cost);
+                            sound_effect_writer.send(SoundEffectEvents::MissileLaunched);
+                            match weapon_type {
+                                WeaponType::Mine => fire_mine(
+                                    &sprites,
+                                    &player_stats,
+                                    &mut commands,
+                                    planets.nearest(*target),
+                                    *target,
+                                ),
+                                WeaponType::Emp => {
+                                    fire_emp(&sprites, &mut commands, *target, &enemies)
+                                }
+                                WeaponType::Railgun => fire_railgun(
+                                    &sprites,
+                                    &player_stats,
+                                    &play_field,
+                                    &mut commands,
+                                    planets.nearest(*target),
+                                    *target,
+                                    &mut enemies,
+                                ),
+                                WeaponType::Missile => {}
+                            }
+                            true
+                        } else {
+                            false
+                        }
+                    }
+                };
+                if fired {
+                    weapon_loadout.start_selected_cooldown();
                 }
             }
             PlayerInputEvents::Scan => {}
+            PlayerInputEvents::ConeScan(_) => {}
+            PlayerInputEvents::PlaceBuoy(_) => {}
             PlayerInputEvents::Shield(_) => {}
+            PlayerInputEvents::SelectWeapon(_) => {}
+            PlayerInputEvents::ToggleShieldMode => {}
         }
     }
 }
 
+/// Fires the standard missile along with the cluster for the owned cluster tier. Only the main missile
+/// follows homing_target. Returns false if there wasn't enough energy to fire
+fn fire_missile(
+    sprites: &Res<AssetHolder>,
+    player_stats: &mut ResMut<PlayerStats>,
+    cluster_tiers: &Res<ClusterTiers>,
+    commands: &mut Commands,
+    launch_location: Vec2,
+    target: Vec2,
+    homing_target: Option<Entity>,
+    fuse_delay: f32,
+    sound_effect_writer: &mut EventWriter<SoundEffectEvents>,
+) -> bool {
+    let fired = PlayerMissile::spawn(
+        sprites,
+        player_stats,
+        commands,
+        launch_location,
+        target,
+        false,
+        homing_target,
+        fuse_delay,
+        sound_effect_writer,
+    );
+    if !fired {
+        return false;
+    }
+    if let Some(cluster_tier) = cluster_tiers.get(player_stats.cluster_missile_level.0) {
+        let spread_multiplier = match player_stats.larger_missiles_level.0 {
+            0 => 1.,
+            1 => 2.,
+            _ => 2.5,
+        };
+        // scatter uses its own rng so player shots don't change the seeded enemy spawns
+        for cluster_target in
+        cluster_tier.targets(launch_location, target, spread_multiplier, &mut rand::thread_rng())
+        {
+            PlayerMissile::spawn(
+                sprites,
+                player_stats,
+                commands,
+                launch_location,
+                cluster_target,
+                true,
+                None,
+                fuse_delay,
+                sound_effect_writer,
+            );
+        }
+    }
+    fired
+}
+
 pub(crate) fn update_missiles(
-    mut missile_query: Query<(Entity, &GlobalTransform, &mut Velocity, &mut PlayerMissile)>,
+    mut missile_query: Query<(
+        Entity,
+        &GlobalTransform,
+        &mut Transform,
+        &mut Velocity,
+        &mut PlayerMissile,
+    )>,
+    // only enemies that are still visible can be tracked
+    visible_enemies: Query<&Transform, (With<Enemy>, With<VisibilityTimer>, Without<PlayerMissile>)>,
     time: Res<Time>,
     mut commands: Commands,
     mut player_stats: ResMut<PlayerStats>,
 ) {
-    for (entity, transform, mut velocity, mut player_missile) in missile_query.iter_mut() {
+    for (entity, transform, mut missile_transform, mut velocity, mut player_missile) in
+    missile_query.iter_mut()
+    {
+        if !player_missile.reached_target {
+            if let Some(homing_target) = player_missile.homing_target {
+                if let Ok(enemy_transform) = visible_enemies.get(homing_target) {
+                    player_missile.target = enemy_transform.translation.truncate();
+                } else {
+                    // lock lost when the enemy dies or fades from view, head for where the missile was originally aimed
+                    player_missile.homing_target = None;
+                    player_missile.target = player_missile.detonation_point;
+                }
+                steer_missile(
+                    &mut velocity,
+                    &mut missile_transform,
+                    transform.translation().truncate(),
+                    player_missile.target,
+                    player_stats.homing_turn_rate * time.delta_seconds(),
+                );
+            }
+        }
+
         if player_missile.reached_target {
             player_missile.time_since_explsion += time.delta_seconds();
-            if player_missile.time_since_explsion >= 0.2 {
+            if player_missile.time_since_explsion >= EXPLOSION_TIME {
                 if player_stats.is_energy_vampire_upgrade && player_missile.enemy_killed {
                     player_stats.plus_one_energy();
                 }
-                commands.entity(player_missile.target_entity).despawn();
+                commands
+                    .entity(player_missile.target_entity)
+                    .despawn_recursive();
                 commands.entity(entity).despawn();
             }
         }
@@ -294,22 +471,119 @@ pub(crate) fn update_missiles(
 
         let magnitude = dif.abs();
 
-        if magnitude.x <= 5.0 && magnitude.y <= 5.0 {
-            player_missile.reached_target = true;
+        if !player_missile.reached_target && magnitude.x <= 5.0 && magnitude.y <= 5.0 {
             velocity.linvel = Vec2::ZERO;
+            // a fused missile sits at the target until the fuse burns down
+            if player_missile.fuse_delay > 0. {
+                player_missile.fuse_delay -= time.delta_seconds();
+            } else {
+                player_missile.reached_target = true;
+            }
         }
     }
 }
 
+fn update_missile_eta_text(
+    missiles: Query<(&GlobalTransform, &Velocity, &PlayerMissile)>,
+    mut eta_texts: Query<(&mut Text, &mut Visibility), With<MissileEtaText>>,
+    player_stats: Res<PlayerStats>,
+) {
+    for (transform, velocity, player_missile) in missiles.iter() {
+        if let Ok((mut text, mut visibility)) = eta_texts.get_mut(player_missile.eta_text) {
+            visibility.is_visible =
+                player_stats.is_missile_eta_display && !player_missile.reached_target;
+            if !visibility.is_visible {
+                continue;
+            }
+            let speed = velocity.linvel.length();
+            text.sections[0].value = if speed > 0. {
+                let distance = transform
+                    .translation()
+                    .truncate()
+                    .distance(player_missile.target);
+                let eta = distance / speed;
+                if player_missile.fuse_delay > 0. {
+                    format!("{:.1}s +{:.1}s", eta, player_missile.fuse_delay)
+                } else {
+                    format!("{:.1}s", eta)
+                }
+            } else {
+                // waiting at the target on its fuse
+                format!("FUSE {:.1}s", player_missile.fuse_delay.max(0.))
+            };
+        }
+    }
+}
+
+/// Turns the missile velocity toward the target by at most max_turn radians, keeping its speed
+fn steer_missile(
+    velocity: &mut Velocity,
+    transform: &mut Transform,
+    position: Vec2,
+    target: Vec2,
+    max_turn: f32,
+) {
+    let to_target = target - position;
+    if to_target == Vec2::ZERO || velocity.linvel == Vec2::ZERO {
+        return;
+    }
+    let turn = velocity
+        .linvel
+        .angle_between(to_target)
+        .clamp(-max_turn, max_turn);
+    velocity.linvel = (Quat::from_rotation_z(turn) * velocity.linvel.extend(0.)).truncate();
+    transform.rotation = Quat::from_rotation_z(velocity.linvel.y.atan2(velocity.linvel.x));
+}
+
+// how long an explosion lasts from the moment the missile reaches its target
+const EXPLOSION_TIME: f32 = 0.3;
+// fraction of the explosion time spent growing, the rest is spent shrinking
+const EXPLOSION_GROW_TIME: f32 = 0.3;
+// fraction of the blast radius that deals full damage before the falloff starts
+const EXPLOSION_FULL_DAMAGE_RADIUS: f32 = 0.5;
+// fraction of the missile damage dealt to an enemy at the very edge of the blast
+const EXPLOSION_EDGE_DAMAGE: f32 = 0.5;
+
+/// The explosion for each Larger Missiles tier: (collider radius in sprite space, sprite size in px).
+/// The sprite is scaled with the blast radius over the explosion's lifetime
+const EXPLOSION_TIERS: [(f32, f32); 3] = [(8., 16.), (12., 32.), (16., 32.)];
+
+/// A missile that has reached its target. The sprite and collider grow to max_radius and shrink again
+#[derive(Component)]
+pub(crate) struct Explosion {
+    max_radius: f32,
+    sprite_size: f32,
+}
+
+impl Explosion {
+    /// How big the blast is as a fraction of max_radius after time seconds
+    fn radius_fraction(time: f32) -> f32 {
+        let grow_time = EXPLOSION_TIME * EXPLOSION_GROW_TIME;
+        let fraction = if time < grow_time {
+            time / grow_time
+        } else {
+            1. - (time - grow_time) / (EXPLOSION_TIME - grow_time)
+        };
+        fraction.clamp(0.2, 1.)
+    }
+}
+
+/// The damage dealt to an enemy distance away from the centre of a blast of max_radius
+fn explosion_damage(base_damage: f32, distance: f32, max_radius: f32) -> f32 {
+    let full_damage_radius = max_radius * EXPLOSION_FULL_DAMAGE_RADIUS;
+    let edge = ((distance - full_damage_radius) / (max_radius - full_damage_radius)).clamp(0., 1.);
+    base_damage * (1. - (1. - EXPLOSION_EDGE_DAMAGE) * edge)
+}
+
 pub(crate) fn missile_explode(
     sprites: Res<AssetHolder>,
     mut missile_query: Query<
         (
             Entity,
             &mut Handle<Image>,
+            &mut Sprite,
             &mut PlayerMissile,
             &mut Velocity,
-            &mut Transform,
         ),
         Changed<PlayerMissile>,
     >,
@@ -317,42 +591,94 @@ pub(crate) fn missile_explode(
     player_stats: Res<PlayerStats>,
     mut sound_effect_writer: EventWriter<SoundEffectEvents>,
 ) {
-    for (entity, mut sprite, mut player_missile, mut velocity, mut transform) in
+    for (entity, mut texture, mut sprite, mut player_missile, mut velocity) in
     missile_query.iter_mut()
     {
-        if player_missile.reached_target {
+        // already_played_explosion_sound doubles as the flag that the explosion was set up
+        if player_missile.reached_target && !player_missile.already_played_explosion_sound {
             velocity.linvel = Vec2::ZERO;
-            let mut radius: f32 = 8.;
-            if player_stats.is_larger_missiles_upgrade {
-                radius = 12.;
-                *sprite = sprites.player_missile_explosion_medium.clone();
-            } else {
-                *sprite = sprites.player_missile_explosion.clone();
-            }
-            if !player_missile.already_played_explosion_sound {
-                sound_effect_writer.send(SoundEffectEvents::MissileExplosion);
-                player_missile.already_played_explosion_sound = true;
-            }
-            commands.entity(entity).insert(Collider::ball(radius));
+            let tier = (player_stats.larger_missiles_level.0 as usize).min(EXPLOSION_TIERS.len() - 1);
+            let (max_radius, sprite_size) = EXPLOSION_TIERS[tier];
+            *texture = match tier {
+                0 => sprites.player_missile_explosion.clone(),
+                1 => sprites.player_missile_explosion_medium.clone(),
+                _ => sprites.player_missile_explosion_large.clone(),
+            };
+            let radius = max_radius * Explosion::radius_fraction(0.);
+            sprite.custom_size = Some(Vec2::splat(sprite_size * radius / max_radius));
+            sound_effect_writer.send(SoundEffectEvents::MissileExplosion);
+            player_missile.already_played_explosion_sound = true;
+            commands
+                .entity(entity)
+                .insert(Collider::ball(radius))
+                .insert(Explosion {
+                    max_radius,
+                    sprite_size,
+                });
         }
     }
 }
 
+pub(crate) fn update_explosions(
+    mut explosions: Query<(&Explosion, &PlayerMissile, &mut Sprite, &mut Collider)>,
+) {
+    for (explosion, player_missile, mut sprite, mut collider) in explosions.iter_mut() {
+        let fraction = Explosion::radius_fraction(player_missile.time_since_explsion);
+        sprite.custom_size = Some(Vec2::splat(explosion.sprite_size * fraction));
+        *collider = Collider::ball(explosion.max_radius * fraction);
+    }
+}
+
 pub(crate) fn handle_missile_collisions(
-    mut missiles: Query<(&CollidingEntities, &mut PlayerMissile), With<PlayerMissile>>,
-    mut enemy_entities: Query<(&Enemy, &Transform)>,
+    mut missiles: Query<(
+        &CollidingEntities,
+        &mut PlayerMissile,
+        &Transform,
+        Option<&Explosion>,
+    )>,
+    mut enemy_entities: Query<
+        (&mut EnemyHealth, &Transform, &Collider),
+        (With<Enemy>, Without<PlayerMissile>),
+    >,
     mut commands: Commands,
-    mut enemy_killed_event_writer: EventWriter<EnemyKilledEvent>,
+    player_stats: Res<PlayerStats>,
 ) {
-    for (entities, mut missiles) in missiles.iter_mut() {
+    for (entities, mut missiles, missile_transform, explosion) in missiles.iter_mut() {
         for collision in entities.iter() {
-            if let Ok(_enemy) = enemy_entities.get(collision) {
+            if missiles.hit_entities.contains(&collision) {
+                continue;
+            }
+            if let Ok((mut enemy_health, enemy_transform, enemy_collider)) =
+                enemy_entities.get_mut(collision)
+            {
                 missiles.reached_target = true;
-                missiles.enemy_killed = true;
-                commands.entity(collision).insert(Destroyed);
-                enemy_killed_event_writer.send(EnemyKilledEvent {
-                    location: _enemy.1.translation.truncate(),
-                });
+                missiles.hit_entities.push(collision);
+
+                // full damage near the centre of the blast down to EXPLOSION_EDGE_DAMAGE at the edge
+                let mut damage = player_stats.missile_damage;
+                if let Some(explosion) = explosion {
+                    let max_radius = explosion.max_radius * missile_transform.scale.x;
+                    let enemy_radius = enemy_collider
+                        .as_ball()
+                        .map_or(0., |ball| ball.radius() * enemy_transform.scale.x);
+                    // measured to the closest edge of the enemy rather than its centre
+                    let distance = (missile_transform
+                        .translation
+                        .truncate()
+                        .distance(enemy_transform.translation.truncate())
+                        - enemy_radius)
+                        .max(0.);
+                    damage = explosion_damage(damage, distance, max_radius);
+                }
+                if damage_enemy(
+                    &mut commands,
+                    collision,
+                    &mut enemy_health,
+                    damage,
+                    KillSource::Weapon,
+                ) {
+                    missiles.enemy_killed = true;
+                }
             }
         }
     }
@@ -363,7 +689,28 @@ fn handle_restart_game_events(
     mut missiles: Query<(Entity, &PlayerMissile)>,
 ) {
     for (missile, player_missile) in missiles.iter_mut() {
-        commands.entity(player_missile.target_entity).despawn();
+        commands
+            .entity(player_missile.target_entity)
+            .despawn_recursive();
         commands.entity(missile).despawn();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn explosion_damage_is_full_inside_the_inner_radius() {
+        assert_eq!(explosion_damage(2., 0., 24.), 2.);
+        assert_eq!(explosion_damage(2., 24. * EXPLOSION_FULL_DAMAGE_RADIUS, 24.), 2.);
+    }
+
+    #[test]
+    fn explosion_damage_is_lower_at_the_edge_than_the_centre() {
+        let centre = explosion_damage(2., 0., 24.);
+        let edge = explosion_damage(2., 24., 24.);
+        assert!(edge < centre);
+        assert_eq!(edge, 2. * EXPLOSION_EDGE_DAMAGE);
+    }
+}
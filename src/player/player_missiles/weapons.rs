@@ -0,0 +1,397 @@
+use crate::enemy::enemy_movement::Slowed;
+use crate::enemy::{damage_enemy, Enemy, EnemyHealth, KillSource};
+use crate::game_systems::PlayField;
+use crate::player::input::input_manager::{PlayerInputEvents, WeaponSelection};
+use crate::sound::SoundEffectEvents;
+use crate::{AssetHolder, GameState, PlayerStats, RestartGameEvent};
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use iyes_loopless::prelude::*;
+use std::time::Duration;
+
+pub(crate) struct WeaponsPlugin;
+
+impl Plugin for WeaponsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WeaponLoadout>();
+        app.add_system_set(
+            ConditionSet::new()
+                .run_in_state(GameState::Playing)
+                .label("weapons")
+                .after("player_input")
+                .with_system(handle_weapon_select_events.run_on_event::<PlayerInputEvents>())
+                .with_system(tick_weapon_cooldowns)
+                .with_system(update_mines)
+                .with_system(update_weapon_effects)
+                .into(),
+        );
+        app.add_system_set(
+            ConditionSet::new()
+                .with_system(handle_restart_game_events.run_on_event::<RestartGameEvent>())
+                .into(),
+        );
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum WeaponType {
+    Missile,
+    // flies to the target and waits there until an enemy gets close
+    Mine,
+    // slows every enemy in a radius around the target
+    Emp,
+    // instantly hits everything on a line from the planet through the target
+    Railgun,
+}
+
+impl WeaponType {
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            WeaponType::Missile => "MISSILE",
+            WeaponType::Mine => "MINE",
+            WeaponType::Emp => "EMP",
+            WeaponType::Railgun => "RAILGUN",
+        }
+    }
+
+    pub(crate) fn energy_cost(&self, player_stats: &PlayerStats) -> u32 {
+        match self {
+            WeaponType::Missile => player_stats.missile_energy_cost,
+            WeaponType::Mine => player_stats.mine_energy_cost,
+            WeaponType::Emp => player_stats.emp_energy_cost,
+            WeaponType::Railgun => player_stats.railgun_energy_cost,
+        }
+    }
+
+    /// Seconds before the weapon can be fired again
+    pub(crate) fn cooldown(&self) -> f32 {
+        match self {
+            WeaponType::Missile => 0.0,
+            WeaponType::Mine => 1.5,
+            WeaponType::Emp => 6.0,
+            WeaponType::Railgun => 4.0,
+        }
+    }
+
+    pub(crate) fn tint(&self) -> Color {
+        match self {
+            WeaponType::Missile => Color::WHITE,
+            WeaponType::Mine => Color::ORANGE,
+            WeaponType::Emp => Color::CYAN,
+            WeaponType::Railgun => Color::rgb(0.7, 0.4, 1.0),
+        }
+    }
+
+    pub(crate) fn sprite(&self, sprites: &AssetHolder) -> Handle<Image> {
+        match self {
+            WeaponType::Missile => sprites.player_missile.clone(),
+            WeaponType::Mine => sprites.player_missile_target.clone(),
+            WeaponType::Emp => sprites.player_missile_explosion_medium.clone(),
+            WeaponType::Railgun => sprites.player_missile_large.clone(),
+        }
+    }
+}
+
+/// The weapons the player can pick from and how long until each can fire again
+pub(crate) struct WeaponLoadout {
+    pub(crate) weapons: Vec<WeaponType>,
+    pub(crate) selected: usize,
+    pub(crate) cooldowns: Vec<f32>,
+}
+
+impl Default for WeaponLoadout {
+    fn default() -> Self {
+        let weapons = vec![
+            WeaponType::Missile,
+            WeaponType::Mine,
+            WeaponType::Emp,
+            WeaponType::Railgun,
+        ];
+        WeaponLoadout {
+            cooldowns: vec![0.; weapons.len()],
+            weapons,
+            selected: 0,
+        }
+    }
+}
+
+impl WeaponLoadout {
+    pub(crate) fn selected_weapon(&self) -> WeaponType {
+        self.weapons[self.selected]
+    }
+
+    pub(crate) fn is_selected_ready(&self) -> bool {
+        self.cooldowns[self.selected] <= 0.
+    }
+
+    pub(crate) fn start_selected_cooldown(&mut self) {
+        self.cooldowns[self.selected] = self.selected_weapon().cooldown();
+    }
+
+    fn select(&mut self, weapon_selection: WeaponSelection) {
+        let weapon_count = self.weapons.len();
+        match weapon_selection {
+            WeaponSelection::Slot(slot) => {
+                if slot < weapon_count {
+                    self.selected = slot;
+                }
+            }
+            WeaponSelection::Next => self.selected = (self.selected + 1) % weapon_count,
+            WeaponSelection::Previous => {
+                self.selected = (self.selected + weapon_count - 1) % weapon_count
+            }
+        }
+    }
+}
+
+#[derive(Component)]
+pub(crate) struct ProximityMine {
+    target: Vec2,
+    speed: f32,
+    is_armed: bool,
+    // how long the mine waits at the target before fizzling out
+    lifetime: Timer,
+}
+
+/// A short lived visual for a weapon like the emp pulse or railgun beam. Fades out and despawns
+#[derive(Component)]
+pub(crate) struct WeaponEffect {
+    fade_timer: Timer,
+}
+
+pub(crate) fn spawn_weapon_effect(
+    commands: &mut Commands,
+    texture: Handle<Image>,
+    color: Color,
+    transform: Transform,
+    fade_time: f32,
+) {
+    commands
+        .spawn_bundle(SpriteBundle {
+            sprite: Sprite { color, ..default() },
+            transform,
+            texture,
+            ..default()
+        })
+        .insert(WeaponEffect {
+            fade_timer: Timer::new(Duration::from_secs_f32(fade_time), false),
+        });
+}
+
+pub(crate) fn fire_mine(
+    sprites: &Res<AssetHolder>,
+    player_stats: &ResMut<PlayerStats>,
+    commands: &mut Commands,
+    launch_location: Vec2,
+    target: Vec2,
+) {
+    commands
+        .spawn_bundle(SpriteBundle {
+            sprite: Sprite {
+                color: WeaponType::Mine.tint(),
+                ..default()
+            },
+            transform: Transform {
+                translation: launch_location.extend(50.),
+                scale: Vec3::new(2., 2., 1.),
+                ..default()
+            },
+            texture: WeaponType::Mine.sprite(sprites),
+            ..default()
+        })
+        .insert(ProximityMine {
+            target,
+            speed: player_stats.missile_speed.0,
+            is_armed: false,
+            lifetime: Timer::new(Duration::from_secs_f32(12.0), false),
+        });
+}
+
+pub(crate) fn fire_emp(
+    sprites: &Res<AssetHolder>,
+    commands: &mut Commands,
+    target: Vec2,
+    enemies: &Query<(Entity, &Transform, &mut EnemyHealth), With<Enemy>>,
+) {
+    let radius = 80.;
+    for (enemy, transform, _) in enemies.iter() {
+        if transform.translation.truncate().distance(target) <= radius {
+            commands.entity(enemy).insert(Slowed {
+                multiplier: 0.4,
+                timer: Timer::new(Duration::from_secs_f32(4.0), false),
+            });
+        }
+    }
+    // the pulse sprite is 32px across
+    spawn_weapon_effect(
+        commands,
+        WeaponType::Emp.sprite(sprites),
+        WeaponType::Emp.tint(),
+        Transform {
+            translation: target.extend(2.0),
+            scale: Vec3::new(radius * 2. / 32., radius * 2. / 32., 1.),
+            ..default()
+        },
+        0.5,
+    );
+}
+
+pub(crate) fn fire_railgun(
+    sprites: &Res<AssetHolder>,
+    player_stats: &ResMut<PlayerStats>,
+    play_field: &Res<PlayField>,
+    commands: &mut Commands,
+    launch_location: Vec2,
+    target: Vec2,
+    enemies: &mut Query<(Entity, &Transform, &mut EnemyHealth), With<Enemy>>,
+) {
+    let direction = (target - launch_location).normalize_or_zero();
+    if direction == Vec2::ZERO {
+        return;
+    }
+    let length = play_field.arena_size;
+    let end = direction * length;
+    for (enemy, transform, mut enemy_health) in enemies.iter_mut() {
+        // worked out relative to the planet the beam comes from
+        let position = transform.translation.truncate() - launch_location;
+        let along = position.dot(direction).clamp(0., length);
+        if position.distance(direction * along) <= 12. {
+            damage_enemy(
+                commands,
+                enemy,
+                &mut enemy_health,
+                player_stats.missile_damage * 2.,
+                KillSource::Weapon,
+            );
+        }
+    }
+    // the slug sprite is 32px, stretched along the beam
+    spawn_weapon_effect(
+        commands,
+        WeaponType::Railgun.sprite(sprites),
+        WeaponType::Railgun.tint(),
+        Transform {
+            translation: (launch_location + end / 2.).extend(2.0),
+            rotation: Quat::from_rotation_z(direction.y.atan2(direction.x)),
+            scale: Vec3::new(length / 32., 0.3, 1.),
+        },
+        0.3,
+    );
+}
+
+fn handle_weapon_select_events(
+    mut player_input_event_reader: EventReader<PlayerInputEvents>,
+    mut weapon_loadout: ResMut<WeaponLoadout>,
+    mut sound_effect_writer: EventWriter<SoundEffectEvents>,
+) {
+    for event in player_input_event_reader.iter() {
+        if let PlayerInputEvents::SelectWeapon(weapon_selection) = event {
+            weapon_loadout.select(*weapon_selection);
+            sound_effect_writer.send(SoundEffectEvents::NormalButton);
+        }
+    }
+}
+
+fn tick_weapon_cooldowns(mut weapon_loadout: ResMut<WeaponLoadout>, time: Res<Time>) {
+    for cooldown in weapon_loadout.cooldowns.iter_mut() {
+        *cooldown = (*cooldown - time.delta_seconds()).max(0.);
+    }
+}
+
+fn update_mines(
+    sprites: Res<AssetHolder>,
+    mut mines: Query<(
+        Entity,
+        &mut ProximityMine,
+        &mut Transform,
+        Option<&CollidingEntities>,
+    )>,
+    mut enemies: Query<&mut EnemyHealth, With<Enemy>>,
+    player_stats: Res<PlayerStats>,
+    mut commands: Commands,
+    mut sound_effect_writer: EventWriter<SoundEffectEvents>,
+    time: Res<Time>,
+) {
+    let trigger_radius = 20.;
+    for (entity, mut mine, mut transform, colliding_entities) in mines.iter_mut() {
+        if !mine.is_armed {
+            let position = transform.translation.truncate();
+            let step = mine.speed * time.delta_seconds();
+            if position.distance(mine.target) <= step {
+                transform.translation = mine.target.extend(transform.translation.z);
+                mine.is_armed = true;
+                commands
+                    .entity(entity)
+                    .insert(Collider::ball(trigger_radius / transform.scale.x))
+                    .insert(Sensor)
+                    .insert(ActiveEvents::COLLISION_EVENTS)
+                    .insert(CollidingEntities::default());
+            } else {
+                let heading = (mine.target - position).normalize_or_zero();
+                transform.translation += (heading * step).extend(0.);
+            }
+            continue;
+        }
+
+        mine.lifetime.tick(time.delta());
+        let mut is_triggered = false;
+        if let Some(colliding_entities) = colliding_entities {
+            for collision in colliding_entities.iter() {
+                if let Ok(mut enemy_health) = enemies.get_mut(collision) {
+                    damage_enemy(
+                        &mut commands,
+                        collision,
+                        &mut enemy_health,
+                        player_stats.missile_damage,
+                        KillSource::Weapon,
+                    );
+                    is_triggered = true;
+                }
+            }
+        }
+
+        if is_triggered {
+            sound_effect_writer.send(SoundEffectEvents::MissileExplosion);
+            spawn_weapon_effect(
+                &mut commands,
+                sprites.player_missile_explosion_medium.clone(),
+                WeaponType::Mine.tint(),
+                Transform {
+                    translation: transform.translation,
+                    scale: Vec3::new(trigger_radius * 2. / 32., trigger_radius * 2. / 32., 1.),
+                    ..default()
+                },
+                0.3,
+            );
+            commands.entity(entity).despawn();
+        } else if mine.lifetime.finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn update_weapon_effects(
+    mut effects: Query<(Entity, &mut WeaponEffect, &mut Sprite)>,
+    mut commands: Commands,
+    time: Res<Time>,
+) {
+    for (entity, mut effect, mut sprite) in effects.iter_mut() {
+        effect.fade_timer.tick(time.delta());
+        if effect.fade_timer.finished() {
+            commands.entity(entity).despawn();
+        } else {
+            sprite.color.set_a(effect.fade_timer.percent_left());
+        }
+    }
+}
+
+fn handle_restart_game_events(
+    mut commands: Commands,
+    mines: Query<Entity, With<ProximityMine>>,
+    effects: Query<Entity, With<WeaponEffect>>,
+) {
+    for entity in mines.iter().chain(effects.iter()) {
+        commands.entity(entity).despawn();
+    }
+    commands.insert_resource(WeaponLoadout::default());
+}
@@ -0,0 +1,257 @@
+use crate::enemy::{damage_enemy, Enemy, EnemyHealth, KillSource, VisibilityTimer};
+use crate::player::planets::Planets;
+use crate::sound::SoundEffectEvents;
+use crate::{GameState, PlayerStats, RestartGameEvent};
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+use bevy_rapier2d::prelude::*;
+use iyes_loopless::prelude::*;
+use std::f32::consts::TAU;
+use std::time::Duration;
+
+pub(crate) struct SatellitePlugin;
+
+impl Plugin for SatellitePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(
+            ConditionSet::new()
+                .run_in_state(GameState::Playing)
+                .label("satellites")
+                .before("post_enemy_loop")
+                .with_system(launch_satellites)
+                .with_system(update_satellites)
+                .with_system(update_interceptors)
+                .with_system(handle_satellite_collisions)
+                .into(),
+        );
+        app.add_system_set(
+            ConditionSet::new()
+                .with_system(handle_restart_game_events.run_on_event::<RestartGameEvent>())
+                .into(),
+        );
+    }
+}
+
+/// A satellite circling the planet that fires interceptors at scanned enemies in range
+#[derive(Component)]
+pub(crate) struct Satellite {
+    // radians around the planet
+    angle: f32,
+    fire_timer: Timer,
+}
+
+/// A short range shot from a satellite that chases one enemy until it hits or runs out of range
+#[derive(Component)]
+pub(crate) struct Interceptor {
+    target: Entity,
+    distance_left: f32,
+}
+
+// how close an interceptor has to get to its target to hit it
+const INTERCEPTOR_HIT_DISTANCE: f32 = 6.;
+
+/// Launches satellites until the number in orbit matches how many were bought, spacing the new ones out
+fn launch_satellites(
+    satellites: Query<&Satellite>,
+    planets: Res<Planets>,
+    player_stats: Res<PlayerStats>,
+    mut commands: Commands,
+) {
+    let in_orbit = satellites.iter().count() as u32;
+    let owned = player_stats.satellite_count.0;
+    if in_orbit >= owned {
+        return;
+    }
+    // slot the new ones in after the last satellite so they don't stack on top of each other
+    let start = satellites
+        .iter()
+        .map(|satellite| satellite.angle)
+        .fold(0., f32::max);
+    for index in in_orbit..owned {
+        let angle = start + TAU * (index + 1) as f32 / player_stats.satellite_count.1 as f32;
+        spawn_satellite(&mut commands, &player_stats, planets.home(), angle);
+    }
+}
+
+fn spawn_satellite(
+    commands: &mut Commands,
+    player_stats: &Res<PlayerStats>,
+    center: Vec2,
+    angle: f32,
+) {
+    commands
+        .spawn_bundle(GeometryBuilder::build_as(
+            &shapes::RegularPolygon {
+                sides: 4,
+                feature: shapes::RegularPolygonFeature::Radius(6.),
+                center: default(),
+            },
+            DrawMode::Outlined {
+                fill_mode: FillMode::color(Color::Rgba {
+                    red: 0.2,
+                    green: 0.5,
+                    blue: 0.8,
+                    alpha: 0.6,
+                }),
+                outline_mode: StrokeMode::new(Color::WHITE, 2.0),
+            },
+            Transform::from_translation(
+                (center
+                    + Vec2::new(angle.cos(), angle.sin()) * player_stats.satellite_orbit_radius)
+                    .extend(2.0),
+            ),
+        ))
+        .insert(Collider::ball(6.))
+        .insert(Sensor)
+        .insert(ActiveEvents::COLLISION_EVENTS)
+        .insert(CollidingEntities::default())
+        .insert(Satellite {
+            angle,
+            fire_timer: Timer::new(
+                Duration::from_secs_f32(player_stats.satellite_fire_rate.0),
+                false,
+            ),
+        });
+}
+
+/// Moves the satellites along their orbit and fires at the closest scanned enemy in range when reloaded
+fn update_satellites(
+    mut satellites: Query<(&mut Transform, &mut Satellite)>,
+    planets: Res<Planets>,
+    visible_enemies: Query<
+        (Entity, &Transform),
+        (With<Enemy>, With<VisibilityTimer>, Without<Satellite>),
+    >,
+    player_stats: Res<PlayerStats>,
+    mut commands: Commands,
+    time: Res<Time>,
+) {
+    // satellites stay with the home planet, moving on to the next one if it falls
+    let center = planets.home();
+    for (mut transform, mut satellite) in satellites.iter_mut() {
+        satellite.angle =
+            (satellite.angle + player_stats.satellite_orbit_speed * time.delta_seconds()) % TAU;
+        let position = center
+            + Vec2::new(satellite.angle.cos(), satellite.angle.sin())
+                * player_stats.satellite_orbit_radius;
+        transform.translation = position.extend(transform.translation.z);
+
+        satellite.fire_timer.tick(time.delta());
+        if !satellite.fire_timer.finished() {
+            continue;
+        }
+        let target = visible_enemies
+            .iter()
+            .map(|(enemy, enemy_transform)| {
+                (
+                    enemy,
+                    enemy_transform.translation.truncate().distance(position),
+                )
+            })
+            .filter(|(_, distance)| *distance <= player_stats.satellite_range)
+            .min_by(|(_, a), (_, b)| a.total_cmp(b));
+        if let Some((enemy, _)) = target {
+            spawn_interceptor(&mut commands, &player_stats, position, enemy);
+            // picks up fire rate upgrades bought since the last shot
+            satellite.fire_timer = Timer::new(
+                Duration::from_secs_f32(player_stats.satellite_fire_rate.0),
+                false,
+            );
+        }
+    }
+}
+
+fn spawn_interceptor(
+    commands: &mut Commands,
+    player_stats: &Res<PlayerStats>,
+    location: Vec2,
+    target: Entity,
+) {
+    commands
+        .spawn_bundle(GeometryBuilder::build_as(
+            &shapes::Circle {
+                radius: 2.0,
+                center: default(),
+            },
+            DrawMode::Fill(FillMode::color(Color::CYAN)),
+            Transform::from_translation(location.extend(3.0)),
+        ))
+        .insert(Interceptor {
+            target,
+            // a bit past the range so a target moving away can still be caught
+            distance_left: player_stats.satellite_range * 1.5,
+        });
+}
+
+fn update_interceptors(
+    mut interceptors: Query<(Entity, &mut Transform, &mut Interceptor), Without<Enemy>>,
+    mut enemies: Query<(&Transform, &mut EnemyHealth), With<Enemy>>,
+    player_stats: Res<PlayerStats>,
+    mut commands: Commands,
+    time: Res<Time>,
+) {
+    for (entity, mut transform, mut interceptor) in interceptors.iter_mut() {
+        let (enemy_transform, mut enemy_health) = match enemies.get_mut(interceptor.target) {
+            Ok(enemy) => enemy,
+            Err(_) => {
+                // the target is already gone
+                commands.entity(entity).despawn();
+                continue;
+            }
+        };
+        let position = transform.translation.truncate();
+        let to_target = enemy_transform.translation.truncate() - position;
+        let step = player_stats.interceptor_speed * time.delta_seconds();
+
+        if to_target.length() <= INTERCEPTOR_HIT_DISTANCE + step {
+            damage_enemy(
+                &mut commands,
+                interceptor.target,
+                &mut enemy_health,
+                player_stats.interceptor_damage,
+                KillSource::Weapon,
+            );
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        interceptor.distance_left -= step;
+        if interceptor.distance_left <= 0. {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        transform.translation += (to_target.normalize_or_zero() * step).extend(0.);
+    }
+}
+
+fn handle_satellite_collisions(
+    satellites: Query<(Entity, &CollidingEntities), With<Satellite>>,
+    enemies: Query<&Enemy>,
+    mut player_stats: ResMut<PlayerStats>,
+    mut commands: Commands,
+    mut sound_effect_writer: EventWriter<SoundEffectEvents>,
+) {
+    for (entity, colliding_entities) in satellites.iter() {
+        if colliding_entities
+            .iter()
+            .any(|collision| enemies.get(collision).is_ok())
+        {
+            commands.entity(entity).despawn();
+            player_stats.satellite_destroyed();
+            sound_effect_writer.send(SoundEffectEvents::ShieldHit);
+        }
+    }
+}
+
+fn handle_restart_game_events(
+    mut commands: Commands,
+    satellites: Query<Entity, With<Satellite>>,
+    interceptors: Query<Entity, With<Interceptor>>,
+) {
+    for satellite in satellites.iter() {
+        commands.entity(satellite).despawn();
+    }
+    for interceptor in interceptors.iter() {
+        commands.entity(interceptor).despawn();
+    }
+}
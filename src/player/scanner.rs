@@ -1 +1,3 @@
 ﻿pub(crate) mod scanner_core;
+pub(crate) mod sensor_buoys;
+pub(crate) mod trajectory_assist;
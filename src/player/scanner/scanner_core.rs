@@ -1,7 +1,11 @@
-﻿use crate::enemy::{Destroyed, Enemy, Ghost, Scanned};
+﻿use crate::enemy::{Destroyed, Enemy, EnemyKilledEvent, Ghost, Scanned};
+use crate::game_systems::PlayField;
 use crate::input::input_manager::PlayerInputEvents::Scan;
 use crate::player::input::input_manager::PlayerInputEvents;
-use crate::player::player_missiles::player_missile_core::{EnemyKilledEvent, PlayerMissile};
+use crate::player::planets::Planets;
+use crate::player::player_missiles::player_missile_core::PlayerMissile;
+use crate::player::scanner::sensor_buoys::SensorBuoyPlugin;
+use crate::player::scanner::trajectory_assist::TrajectoryAssistPlugin;
 use crate::sound::SoundEffectEvents;
 use crate::Keyframes::Translation;
 use crate::{AssetHolder, GameState, PlayerStats, RestartGameEvent};
@@ -16,7 +20,9 @@ pub(crate) struct ScannerPlugin;
 impl Plugin for ScannerPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(Msaa { samples: 4 })
-            .add_plugin(ShapePlugin);
+            .add_plugin(ShapePlugin)
+            .add_plugin(TrajectoryAssistPlugin)
+            .add_plugin(SensorBuoyPlugin);
         app.add_system_set(
             ConditionSet::new()
                 .run_in_state(GameState::Playing)
@@ -42,11 +48,39 @@ pub(crate) struct ScanComp {
     size: f32,
     max_size: f32,
     location: Vec2,
+    speed_multiplier: f32,
+    // None for a full 360 scan
+    cone: Option<ScanCone>,
+}
+
+/// The direction and width of a cone scan, both in radians
+#[derive(Clone, Copy)]
+pub(crate) struct ScanCone {
+    direction: f32,
+    half_angle: f32,
+}
+
+// how many straight segments the cone's arc is made of
+const CONE_ARC_SEGMENTS: usize = 12;
+
+impl ScanCone {
+    /// The outline of the cone at radius, relative to the scan location. Used for both the path and the collider
+    fn points(&self, radius: f32) -> Vec<Vec2> {
+        let mut points = vec![Vec2::ZERO];
+        for segment in 0..=CONE_ARC_SEGMENTS {
+            let angle = self.direction - self.half_angle
+                + 2. * self.half_angle * segment as f32 / CONE_ARC_SEGMENTS as f32;
+            points.push(Vec2::new(angle.cos(), angle.sin()) * radius);
+        }
+        points
+    }
 }
 
 pub(crate) fn handle_player_scan_spawn_events(
     sprites: Res<AssetHolder>,
     mut player_stats: ResMut<PlayerStats>,
+    play_field: Res<PlayField>,
+    planets: Res<Planets>,
     mut commands: Commands,
     mut player_input_event_reader: EventReader<PlayerInputEvents>,
     mut sound_effect_writer: EventWriter<SoundEffectEvents>,
@@ -54,18 +88,67 @@ pub(crate) fn handle_player_scan_spawn_events(
     for event in player_input_event_reader.iter() {
         match event {
             PlayerInputEvents::FireMissile(_) => {}
+            PlayerInputEvents::FireHomingMissile(_) => {}
+            PlayerInputEvents::FireFusedMissile(_) => {}
             PlayerInputEvents::Scan => {
                 if player_stats.check_if_enough_energy(player_stats.scan_energy_cost) {
                     player_stats.scanner_fired();
-                    scan(&mut commands, Vec2 { x: 0., y: 0. }, 1000.);
+                    // every planet still standing sends out its own ring
+                    for location in planets.living() {
+                        scan(&mut commands, location, play_field.arena_size);
+                    }
                     sound_effect_writer.send(SoundEffectEvents::ScanStarted);
                 }
             }
+            PlayerInputEvents::ConeScan(target) => {
+                let location = planets.nearest(*target);
+                let direction = *target - location;
+                if direction == Vec2::ZERO {
+                    continue;
+                }
+                if player_stats.check_if_enough_energy(player_stats.cone_scan_energy_cost) {
+                    player_stats.cone_scanner_fired();
+                    cone_scan(
+                        &mut commands,
+                        location,
+                        ScanCone {
+                            direction: direction.y.atan2(direction.x),
+                            half_angle: player_stats.cone_scan_half_angle,
+                        },
+                        play_field.arena_size * player_stats.cone_scan_range_multiplier,
+                        player_stats.cone_scan_speed_multiplier,
+                    );
+                    sound_effect_writer.send(SoundEffectEvents::ScanStarted);
+                }
+            }
+            PlayerInputEvents::PlaceBuoy(_) => {}
             PlayerInputEvents::Shield(_) => {}
+            PlayerInputEvents::SelectWeapon(_) => {}
+            PlayerInputEvents::ToggleShieldMode => {}
         }
     }
 }
 
+fn scan_draw_mode() -> DrawMode {
+    DrawMode::Outlined {
+        fill_mode: FillMode::color(Color::Rgba {
+            red: 0.0,
+            green: 0.0,
+            blue: 0.0,
+            alpha: 0.0,
+        }),
+        outline_mode: StrokeMode::new(
+            Color::Rgba {
+                red: 0.3,
+                green: 0.6,
+                blue: 0.25,
+                alpha: 1.0,
+            },
+            3.0,
+        ),
+    }
+}
+
 pub(crate) fn scan(mut commands: &mut Commands, location: Vec2, max_size: f32) {
     commands
         .spawn_bundle(GeometryBuilder::build_as(
@@ -73,23 +156,7 @@ pub(crate) fn scan(mut commands: &mut Commands, location: Vec2, max_size: f32) {
                 radius: 10.0,
                 center: default(),
             },
-            DrawMode::Outlined {
-                fill_mode: FillMode::color(Color::Rgba {
-                    red: 0.0,
-                    green: 0.0,
-                    blue: 0.0,
-                    alpha: 0.0,
-                }),
-                outline_mode: StrokeMode::new(
-                    Color::Rgba {
-                        red: 0.3,
-                        green: 0.6,
-                        blue: 0.25,
-                        alpha: 1.0,
-                    },
-                    3.0,
-                ),
-            },
+            scan_draw_mode(),
             Transform {
                 translation: location.extend(1.0),
                 ..default()
@@ -101,11 +168,47 @@ pub(crate) fn scan(mut commands: &mut Commands, location: Vec2, max_size: f32) {
             size: 20.0,
             max_size,
             location,
+            speed_multiplier: 1.0,
+            cone: None,
         })
         .insert(Collider::ball(20.0))
         .insert(Sensor);
 }
 
+/// A narrow scan that sweeps out from location along the cone direction
+pub(crate) fn cone_scan(
+    commands: &mut Commands,
+    location: Vec2,
+    cone: ScanCone,
+    max_size: f32,
+    speed_multiplier: f32,
+) {
+    let points = cone.points(20.0);
+    commands
+        .spawn_bundle(GeometryBuilder::build_as(
+            &shapes::Polygon {
+                points: points.clone(),
+                closed: true,
+            },
+            scan_draw_mode(),
+            Transform {
+                translation: location.extend(1.0),
+                ..default()
+            },
+        ))
+        .insert(CollidingEntities::default())
+        .insert(ActiveEvents::COLLISION_EVENTS)
+        .insert(ScanComp {
+            size: 20.0,
+            max_size,
+            location,
+            speed_multiplier,
+            cone: Some(cone),
+        })
+        .insert(Collider::convex_hull(&points).unwrap_or_else(|| Collider::ball(20.0)))
+        .insert(Sensor);
+}
+
 pub(crate) fn increase_scan_radius(
     mut query: Query<(Entity, &mut Path, &mut ScanComp, &mut Collider)>,
     time: Res<Time>,
@@ -113,19 +216,30 @@ pub(crate) fn increase_scan_radius(
     mut commands: Commands,
 ) {
     for (entity, mut path, mut scan, mut collider) in query.iter_mut() {
-        scan.size += player_stats.scan_speed.0 * time.delta_seconds();
+        scan.size += player_stats.scan_speed.0 * scan.speed_multiplier * time.delta_seconds();
         let new_size = scan.size;
-        let new_circle = shapes::Circle {
-            radius: new_size,
-            center: default(),
-        };
 
         if scan.size >= scan.max_size {
             commands.entity(entity).despawn();
         }
 
-        *path = ShapePath::build_as(&new_circle);
-        *collider = Collider::ball(new_size);
+        if let Some(cone) = scan.cone {
+            let points = cone.points(new_size);
+            if let Some(cone_collider) = Collider::convex_hull(&points) {
+                *collider = cone_collider;
+            }
+            *path = ShapePath::build_as(&shapes::Polygon {
+                points,
+                closed: true,
+            });
+        } else {
+            let new_circle = shapes::Circle {
+                radius: new_size,
+                center: default(),
+            };
+            *path = ShapePath::build_as(&new_circle);
+            *collider = Collider::ball(new_size);
+        }
     }
 }
 
@@ -146,30 +260,38 @@ pub(crate) fn handle_scanner_collisions(
         match collision_event {
             CollisionEvent::Started(a, b, _) => {
                 if let Ok(_enemy) = enemy_entities.get(*a) {
-                    if let Ok(_scan) = scan.get(*b) {
+                    if let Ok(scan) = scan.get(*b) {
                         info!("did scan an enemy");
-                        commands.entity(*a).insert(Scanned);
+                        commands.entity(*a).insert(Scanned {
+                            origin: scan.location,
+                        });
                         sound_effect_writer.send(SoundEffectEvents::ScanEnemy);
                     }
                 }
                 if let Ok(_enemy) = enemy_entities.get(*b) {
-                    if let Ok(_scan) = scan.get(*a) {
+                    if let Ok(scan) = scan.get(*a) {
                         info!("did scan an enemy");
-                        commands.entity(*b).insert(Scanned);
+                        commands.entity(*b).insert(Scanned {
+                            origin: scan.location,
+                        });
                         sound_effect_writer.send(SoundEffectEvents::ScanEnemy);
                     }
                 }
                 //handles testing for ghost entities
                 if let Ok(_enemy) = ghost_entities.get(*a) {
-                    if let Ok(_scan) = scan.get(*b) {
+                    if let Ok(scan) = scan.get(*b) {
                         info!("did scan an ghost");
-                        commands.entity(*a).insert(Scanned);
+                        commands.entity(*a).insert(Scanned {
+                            origin: scan.location,
+                        });
                     }
                 }
                 if let Ok(_enemy) = ghost_entities.get(*b) {
-                    if let Ok(_scan) = scan.get(*a) {
+                    if let Ok(scan) = scan.get(*a) {
                         info!("did scan a ghost");
-                        commands.entity(*b).insert(Scanned);
+                        commands.entity(*b).insert(Scanned {
+                            origin: scan.location,
+                        });
                     }
                 }
             }
@@ -0,0 +1,145 @@
+use crate::enemy::Enemy;
+use crate::player::input::input_manager::PlayerInputEvents;
+use crate::player::scanner::scanner_core::scan;
+use crate::sound::SoundEffectEvents;
+use crate::{GameState, PlayerStats, RestartGameEvent};
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+use bevy_rapier2d::prelude::*;
+use iyes_loopless::prelude::*;
+use std::time::Duration;
+
+pub(crate) struct SensorBuoyPlugin;
+
+impl Plugin for SensorBuoyPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(
+            ConditionSet::new()
+                .run_in_state(GameState::Playing)
+                .label("sensor_buoys")
+                .after("player_input")
+                .with_system(handle_place_buoy_events.run_on_event::<PlayerInputEvents>())
+                .with_system(update_buoys)
+                .with_system(handle_buoy_collisions)
+                .into(),
+        );
+        app.add_system_set(
+            ConditionSet::new()
+                .with_system(handle_restart_game_events.run_on_event::<RestartGameEvent>())
+                .into(),
+        );
+    }
+}
+
+/// A buoy placed in the field that scans around itself every pulse and drains energy while it lives
+#[derive(Component)]
+pub(crate) struct SensorBuoy {
+    pulse_timer: Timer,
+    drain_timer: Timer,
+}
+
+fn handle_place_buoy_events(
+    mut player_input_event_reader: EventReader<PlayerInputEvents>,
+    buoys: Query<&SensorBuoy>,
+    mut player_stats: ResMut<PlayerStats>,
+    mut commands: Commands,
+    mut sound_effect_writer: EventWriter<SoundEffectEvents>,
+) {
+    let mut buoy_count = buoys.iter().count() as u32;
+    for event in player_input_event_reader.iter() {
+        if let PlayerInputEvents::PlaceBuoy(location) = event {
+            if buoy_count >= player_stats.buoy_limit
+                || !player_stats.check_if_enough_energy(player_stats.buoy_energy_cost)
+            {
+                sound_effect_writer.send(SoundEffectEvents::ErrorButton);
+                continue;
+            }
+            player_stats.buoy_placed();
+            buoy_count += 1;
+            spawn_buoy(&mut commands, &player_stats, *location);
+            sound_effect_writer.send(SoundEffectEvents::ScanStarted);
+        }
+    }
+}
+
+fn spawn_buoy(commands: &mut Commands, player_stats: &ResMut<PlayerStats>, location: Vec2) {
+    commands
+        .spawn_bundle(GeometryBuilder::build_as(
+            &shapes::Circle {
+                radius: 6.0,
+                center: default(),
+            },
+            DrawMode::Outlined {
+                fill_mode: FillMode::color(Color::Rgba {
+                    red: 0.3,
+                    green: 0.6,
+                    blue: 0.25,
+                    alpha: 0.6,
+                }),
+                outline_mode: StrokeMode::new(Color::WHITE, 2.0),
+            },
+            Transform {
+                translation: location.extend(2.0),
+                ..default()
+            },
+        ))
+        .insert(Collider::ball(8.))
+        .insert(Sensor)
+        .insert(ActiveEvents::COLLISION_EVENTS)
+        .insert(CollidingEntities::default())
+        .insert(SensorBuoy {
+            pulse_timer: Timer::new(Duration::from_secs_f32(player_stats.buoy_pulse_rate), true),
+            drain_timer: Timer::new(Duration::from_secs_f32(player_stats.buoy_drain_rate), true),
+        });
+}
+
+fn update_buoys(
+    mut buoys: Query<(Entity, &Transform, &mut SensorBuoy)>,
+    mut player_stats: ResMut<PlayerStats>,
+    mut commands: Commands,
+    time: Res<Time>,
+) {
+    for (entity, transform, mut buoy) in buoys.iter_mut() {
+        buoy.drain_timer.tick(time.delta());
+        if buoy.drain_timer.just_finished() {
+            // a buoy the planet can't power shuts down
+            if !player_stats.check_if_enough_energy(1) {
+                commands.entity(entity).despawn();
+                continue;
+            }
+            player_stats.buoy_drained();
+        }
+
+        buoy.pulse_timer.tick(time.delta());
+        if buoy.pulse_timer.just_finished() {
+            scan(
+                &mut commands,
+                transform.translation.truncate(),
+                player_stats.buoy_scan_size,
+            );
+        }
+    }
+}
+
+fn handle_buoy_collisions(
+    buoys: Query<(Entity, &CollidingEntities), With<SensorBuoy>>,
+    enemies: Query<&Enemy>,
+    mut commands: Commands,
+    mut sound_effect_writer: EventWriter<SoundEffectEvents>,
+) {
+    for (entity, colliding_entities) in buoys.iter() {
+        if colliding_entities
+            .iter()
+            .any(|collision| enemies.get(collision).is_ok())
+        {
+            commands.entity(entity).despawn();
+            sound_effect_writer.send(SoundEffectEvents::ShieldHit);
+        }
+    }
+}
+
+fn handle_restart_game_events(mut commands: Commands, buoys: Query<Entity, With<SensorBuoy>>) {
+    for buoy in buoys.iter() {
+        commands.entity(buoy).despawn();
+    }
+}
@@ -0,0 +1,207 @@
+use crate::enemy::{Enemy, Ghost, Scanned};
+use crate::player::planets::Planets;
+use crate::{GameState, PlayerStats, RestartGameEvent};
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+use bevy_rapier2d::prelude::*;
+use iyes_loopless::prelude::*;
+use std::time::Duration;
+
+pub(crate) struct TrajectoryAssistPlugin;
+
+impl Plugin for TrajectoryAssistPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(
+            ConditionSet::new()
+                .run_in_state(GameState::Playing)
+                .label("trajectory_assist")
+                // after the ghosts are moved to their fuzzed scan positions
+                .after("main_enemy_loop")
+                .with_system(spawn_trajectory_lines)
+                .with_system(fade_trajectory_lines)
+                .into(),
+        );
+        app.add_system_set(
+            ConditionSet::new()
+                .with_system(handle_restart_game_events.run_on_event::<RestartGameEvent>())
+                .into(),
+        );
+    }
+}
+
+// how many seconds ahead of the enemy the line is drawn
+const TRAJECTORY_LOOKAHEAD: f32 = 3.0;
+
+/// A line or intercept marker left behind by a scan. Fades out over its fade_timer
+#[derive(Component)]
+pub(crate) struct TrajectoryLine {
+    enemy: Entity,
+    fade_timer: Timer,
+}
+
+fn spawn_trajectory_lines(
+    scanned_enemies: Query<(Entity, &Enemy, &Velocity), With<Scanned>>,
+    ghosts: Query<&Transform, With<Ghost>>,
+    old_lines: Query<(Entity, &TrajectoryLine)>,
+    player_stats: Res<PlayerStats>,
+    planets: Res<Planets>,
+    mut commands: Commands,
+) {
+    if player_stats.trajectory_assist_level.0 == 0 {
+        return;
+    }
+    for (enemy_entity, enemy, velocity) in scanned_enemies.iter() {
+        // the prediction is only as good as the scan, so it starts from the ghost
+        let start = match ghosts.get(enemy.scan_ghost) {
+            Ok(ghost_transform) => ghost_transform.translation.truncate(),
+            Err(_) => continue,
+        };
+        // a new scan replaces the old prediction for this enemy
+        for (line_entity, line) in old_lines.iter() {
+            if line.enemy == enemy_entity {
+                commands.entity(line_entity).despawn();
+            }
+        }
+
+        let end = start + velocity.linvel * TRAJECTORY_LOOKAHEAD;
+        commands
+            .spawn_bundle(GeometryBuilder::build_as(
+                &shapes::Line(start, end),
+                DrawMode::Stroke(StrokeMode::new(
+                    Color::Rgba {
+                        red: 0.3,
+                        green: 0.6,
+                        blue: 0.25,
+                        alpha: 0.8,
+                    },
+                    2.0,
+                )),
+                Transform::from_xyz(0., 0., 2.0),
+            ))
+            .insert(TrajectoryLine {
+                enemy: enemy_entity,
+                fade_timer: Timer::new(Duration::from_secs_f32(1.5), false),
+            });
+
+        if player_stats.trajectory_assist_level.0 >= 2 {
+            // missiles launch from the planet nearest the click, which is near wherever the enemy is
+            let launch_location = planets.nearest(start);
+            if let Some(intercept_point) = intercept_point(
+                start - launch_location,
+                velocity.linvel,
+                player_stats.missile_speed.0,
+            )
+                .map(|intercept_point| intercept_point + launch_location)
+            {
+                commands
+                    .spawn_bundle(GeometryBuilder::build_as(
+                        &shapes::Circle {
+                            radius: 6.0,
+                            center: default(),
+                        },
+                        DrawMode::Stroke(StrokeMode::new(
+                            Color::Rgba {
+                                red: 0.9,
+                                green: 0.8,
+                                blue: 0.2,
+                                alpha: 0.8,
+                            },
+                            2.0,
+                        )),
+                        Transform::from_translation(intercept_point.extend(2.0)),
+                    ))
+                    .insert(TrajectoryLine {
+                        enemy: enemy_entity,
+                        fade_timer: Timer::new(Duration::from_secs_f32(1.5), false),
+                    });
+            }
+        }
+    }
+}
+
+/// Where a missile fired from the planet right now would meet an enemy at position moving at velocity.
+/// position is relative to the planet. None if the missile can never catch it
+pub(crate) fn intercept_point(position: Vec2, velocity: Vec2, missile_speed: f32) -> Option<Vec2> {
+    // |position + velocity * t| = missile_speed * t, solved for the smallest positive t
+    let a = velocity.dot(velocity) - missile_speed * missile_speed;
+    let b = 2.0 * position.dot(velocity);
+    let c = position.dot(position);
+
+    let time = if a.abs() < f32::EPSILON {
+        if b.abs() < f32::EPSILON {
+            return None;
+        }
+        -c / b
+    } else {
+        let discriminant = b * b - 4.0 * a * c;
+        if discriminant < 0. {
+            return None;
+        }
+        let root = discriminant.sqrt();
+        let first = (-b - root) / (2.0 * a);
+        let second = (-b + root) / (2.0 * a);
+        match (first > 0., second > 0.) {
+            (true, true) => first.min(second),
+            (true, false) => first,
+            (false, true) => second,
+            (false, false) => return None,
+        }
+    };
+
+    if time <= 0. {
+        return None;
+    }
+    Some(position + velocity * time)
+}
+
+fn fade_trajectory_lines(
+    mut lines: Query<(Entity, &mut TrajectoryLine, &mut DrawMode)>,
+    mut commands: Commands,
+    time: Res<Time>,
+) {
+    for (entity, mut line, mut draw_mode) in lines.iter_mut() {
+        line.fade_timer.tick(time.delta());
+        if line.fade_timer.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        if let DrawMode::Stroke(ref mut stroke_mode) = *draw_mode {
+            stroke_mode.color.set_a(0.8 * line.fade_timer.percent_left());
+        }
+    }
+}
+
+fn handle_restart_game_events(mut commands: Commands, lines: Query<Entity, With<TrajectoryLine>>) {
+    for line in lines.iter() {
+        commands.entity(line).despawn();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intercept_point_hits_a_still_enemy_where_it_is() {
+        let point = intercept_point(Vec2::new(100., 0.), Vec2::ZERO, 50.).unwrap();
+        assert!(point.distance(Vec2::new(100., 0.)) < 0.001);
+    }
+
+    #[test]
+    fn intercept_point_meets_an_incoming_enemy_part_way() {
+        // same speed as the missile so this takes the linear branch
+        let point = intercept_point(Vec2::new(100., 0.), Vec2::new(-50., 0.), 50.).unwrap();
+        assert!(point.distance(Vec2::new(50., 0.)) < 0.001);
+    }
+
+    #[test]
+    fn intercept_point_is_none_for_an_enemy_outrunning_the_missile() {
+        assert!(intercept_point(Vec2::new(100., 0.), Vec2::new(80., 0.), 50.).is_none());
+    }
+
+    #[test]
+    fn intercept_point_is_none_when_the_only_time_is_negative() {
+        // moving away at exactly missile speed, the linear solve lands in the past
+        assert!(intercept_point(Vec2::new(100., 0.), Vec2::new(50., 0.), 50.).is_none());
+    }
+}
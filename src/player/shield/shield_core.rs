@@ -1,22 +1,57 @@
-﻿use crate::enemy::{Destroyed, Enemy};
+﻿use crate::enemy::{damage_enemy, Enemy, EnemyHealth, KillSource, Scanned};
+use crate::helpers::mouse_screen_pos_to_world_pos;
 use crate::player::input::input_manager::PlayerInputEvents;
-use crate::{GameState, PlayerStats};
+use crate::player::planets::Planets;
+use crate::player::player_missiles::weapons::spawn_weapon_effect;
+use crate::{AssetHolder, GameState, PlayerStats, RestartGameEvent};
 use bevy::prelude::*;
 use bevy_prototype_lyon::prelude::FillMode;
 use bevy_prototype_lyon::prelude::*;
 use bevy_rapier2d::prelude::*;
 use iyes_loopless::prelude::*;
 use crate::sound::SoundEffectEvents;
+use std::f32::consts::{PI, TAU};
 
 pub(crate) struct ShieldPlugin;
 
 #[derive(Component)]
 pub(crate) struct ShieldComp;
 
+/// The small chevron outside the shield that shows where the arc is aimed
+#[derive(Component)]
+pub(crate) struct ShieldFacingIndicator;
+
+const SHIELD_RADIUS: f32 = 60.;
+
+// how many straight segments the arc shield is made of
+const SHIELD_ARC_SEGMENTS: usize = 12;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ShieldMode {
+    // a section of the shield aimed with the mouse. Costs much less energy
+    Arc,
+    // the full circle, the default
+    Bubble,
+}
+
 #[derive(Component)]
 pub(crate) struct ShieldRes {
     is_active: bool,
     time_till_next_cost: f32,
+    // enemies currently inside the shield that were already hit by it
+    blocked_enemies: Vec<Entity>,
+    mode: ShieldMode,
+    // the planet the shield sits on
+    center: Vec2,
+    // angle in radians from the planet to the mouse
+    facing: f32,
+    // above zero while the shield is overloaded and can't be raised
+    overload_time_left: f32,
+    time_till_next_integrity: f32,
+    // seconds since startup when the shield was last raised, for timing counter pulses
+    raised_at: f64,
+    // one counter pulse per raise
+    is_counter_pulse_ready: bool,
 }
 
 impl FromWorld for ShieldRes {
@@ -24,6 +59,37 @@ impl FromWorld for ShieldRes {
         ShieldRes {
             is_active: false,
             time_till_next_cost: 0.0,
+            blocked_enemies: Vec::new(),
+            mode: ShieldMode::Bubble,
+            center: Vec2::ZERO,
+            facing: 0.0,
+            overload_time_left: 0.0,
+            time_till_next_integrity: 0.0,
+            raised_at: 0.0,
+            is_counter_pulse_ready: false,
+        }
+    }
+}
+
+impl ShieldRes {
+    pub(crate) fn mode(&self) -> ShieldMode {
+        self.mode
+    }
+
+    pub(crate) fn overload_time_left(&self) -> f32 {
+        self.overload_time_left
+    }
+
+    /// If a point is covered by the shield in its current mode
+    fn covers(&self, point: Vec2, half_angle: f32) -> bool {
+        match self.mode {
+            ShieldMode::Bubble => true,
+            ShieldMode::Arc => {
+                let point = point - self.center;
+                // wrap the difference into -PI..PI so aiming across the negative x axis works
+                let difference = (point.y.atan2(point.x) - self.facing + PI).rem_euclid(TAU) - PI;
+                difference.abs() <= half_angle
+            }
         }
     }
 }
@@ -41,6 +107,8 @@ impl Plugin for ShieldPlugin {
                 .label("shield_loop")
                 .with_system(handle_player_shield_events.run_on_event::<PlayerInputEvents>())
                 .with_system(shield_count_cost.run_if(is_shield_active))
+                .with_system(aim_shield)
+                .with_system(recharge_shield_integrity.run_if_not(is_shield_active))
                 .into(),
         );
 
@@ -52,14 +120,66 @@ impl Plugin for ShieldPlugin {
                 .with_system(handle_player_shield_collisions.run_if(is_shield_active))
                 .into(),
         );
+        app.add_system_set(
+            ConditionSet::new()
+                .with_system(handle_restart_game_events.run_on_event::<RestartGameEvent>())
+                .into(),
+        );
     }
 }
 
-fn setup_shield(mut commands: Commands) {
+/// The shield outline for a mode. The arc is built around the +x axis and rotated to the facing by the transform
+fn shield_path(mode: ShieldMode, half_angle: f32) -> Path {
+    match mode {
+        ShieldMode::Bubble => ShapePath::build_as(&shapes::Circle {
+            radius: SHIELD_RADIUS,
+            center: Default::default(),
+        }),
+        ShieldMode::Arc => {
+            let points = (0..=SHIELD_ARC_SEGMENTS)
+                .map(|segment| {
+                    let angle = -half_angle
+                        + 2. * half_angle * segment as f32 / SHIELD_ARC_SEGMENTS as f32;
+                    Vec2::new(angle.cos(), angle.sin()) * SHIELD_RADIUS
+                })
+                .collect();
+            ShapePath::build_as(&shapes::Polygon {
+                points,
+                closed: false,
+            })
+        }
+    }
+}
+
+fn setup_shield(mut commands: Commands, player_stats: Res<PlayerStats>) {
+    let facing_indicator = commands
+        .spawn_bundle(GeometryBuilder::build_as(
+            &shapes::Polygon {
+                points: vec![
+                    Vec2::new(SHIELD_RADIUS + 6., -5.),
+                    Vec2::new(SHIELD_RADIUS + 12., 0.),
+                    Vec2::new(SHIELD_RADIUS + 6., 5.),
+                ],
+                closed: false,
+            },
+            DrawMode::Stroke(StrokeMode::new(
+                Color::Rgba {
+                    red: 1.0,
+                    green: 1.0,
+                    blue: 1.0,
+                    alpha: 0.5,
+                },
+                2.0,
+            )),
+            Transform::default(),
+        ))
+        .insert(ShieldFacingIndicator)
+        .id();
+
     commands
         .spawn_bundle(GeometryBuilder::build_as(
             &shapes::Circle {
-                radius: 60.0,
+                radius: SHIELD_RADIUS,
                 center: Default::default(),
             },
             DrawMode::Outlined {
@@ -81,17 +201,66 @@ fn setup_shield(mut commands: Commands) {
             },
             Transform::default(),
         ))
+        .insert(shield_path(ShieldMode::Bubble, player_stats.arc_shield_half_angle))
         .insert(ShieldComp)
         .insert(Visibility { is_visible: false })
         .insert(CollidingEntities::default())
         .insert(ActiveEvents::COLLISION_EVENTS)
-        .insert(Sensor);
+        .insert(Sensor)
+        .add_child(facing_indicator);
 }
 
 fn is_shield_active(shield_resource: Res<ShieldRes>) -> bool {
     shield_resource.is_active
 }
 
+/// Keeps the shield on the home planet and turns it to face the mouse. The indicator follows since it's a child
+fn aim_shield(
+    windows: Res<Windows>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+    planets: Res<Planets>,
+    mut shield_resource: ResMut<ShieldRes>,
+    mut shield_query: Query<&mut Transform, With<ShieldComp>>,
+    mut indicator_query: Query<&mut Visibility, With<ShieldFacingIndicator>>,
+) {
+    let mouse_world_pos = mouse_screen_pos_to_world_pos(&windows, &camera_query);
+    // zero means the cursor left the window so keep the last facing
+    shield_resource.center = planets.home();
+    if mouse_world_pos != Vec2::ZERO {
+        let aim = mouse_world_pos - shield_resource.center;
+        shield_resource.facing = aim.y.atan2(aim.x);
+    }
+    for mut transform in shield_query.iter_mut() {
+        transform.translation = shield_resource.center.extend(transform.translation.z);
+        transform.rotation = Quat::from_rotation_z(shield_resource.facing);
+    }
+    for mut visibility in indicator_query.iter_mut() {
+        visibility.is_visible = shield_resource.mode == ShieldMode::Arc;
+    }
+}
+
+/// Counts down an overload, refilling the shield once it's over. Otherwise slowly regains integrity while the shield is down
+fn recharge_shield_integrity(
+    time: Res<Time>,
+    mut player_stats: ResMut<PlayerStats>,
+    mut shield_resource: ResMut<ShieldRes>,
+) {
+    if shield_resource.overload_time_left > 0.0 {
+        shield_resource.overload_time_left -= time.delta_seconds();
+        if shield_resource.overload_time_left <= 0.0 {
+            shield_resource.overload_time_left = 0.0;
+            shield_resource.time_till_next_integrity = 0.0;
+            player_stats.current_shield_integrity = player_stats.shield_max_integrity;
+        }
+        return;
+    }
+    shield_resource.time_till_next_integrity += time.delta_seconds();
+    if shield_resource.time_till_next_integrity >= player_stats.shield_integrity_recharge_rate {
+        shield_resource.time_till_next_integrity -= player_stats.shield_integrity_recharge_rate;
+        player_stats.recharge_shield_integrity();
+    }
+}
+
 fn shield_count_cost(
     time: Res<Time>,
     mut player_stats: ResMut<PlayerStats>,
@@ -99,10 +268,11 @@ fn shield_count_cost(
     mut shield_query: Query<(Entity, &mut Visibility), With<ShieldComp>>,
     mut commands: Commands,
 ) {
+    let cost_rate = player_stats.shield_cost_rate_for(shield_resource.mode == ShieldMode::Arc);
     shield_resource.time_till_next_cost += time.delta_seconds();
-    if shield_resource.time_till_next_cost >= player_stats.shield_cost_rate {
+    if shield_resource.time_till_next_cost >= cost_rate {
         if player_stats.check_if_enough_energy(player_stats.shield_energy_cost) {
-            shield_resource.time_till_next_cost -= player_stats.shield_cost_rate;
+            shield_resource.time_till_next_cost -= cost_rate;
             player_stats.shield_cost();
             shield(&mut shield_query, &mut commands);
         } else {
@@ -114,9 +284,11 @@ fn shield_count_cost(
 }
 
 pub(crate) fn handle_player_shield_events(
+    time: Res<Time>,
     mut shield_resource: ResMut<ShieldRes>,
     mut player_stats: ResMut<PlayerStats>,
     mut shield_query: Query<(Entity, &mut Visibility), With<ShieldComp>>,
+    mut shield_paths: Query<&mut Path, With<ShieldComp>>,
     mut commands: Commands,
     mut player_input_event_reader: EventReader<PlayerInputEvents>,
     mut sound_effect_writer: EventWriter<SoundEffectEvents>,
@@ -124,24 +296,47 @@ pub(crate) fn handle_player_shield_events(
     for event in player_input_event_reader.iter() {
         match event {
             PlayerInputEvents::FireMissile(_) => {}
+            PlayerInputEvents::FireHomingMissile(_) => {}
+            PlayerInputEvents::FireFusedMissile(_) => {}
             PlayerInputEvents::Scan => {}
+            PlayerInputEvents::ConeScan(_) => {}
+            PlayerInputEvents::PlaceBuoy(_) => {}
+            PlayerInputEvents::SelectWeapon(_) => {}
             PlayerInputEvents::Shield(state) => {
                 if *state == true {
+                    if shield_resource.overload_time_left > 0.0
+                        || player_stats.current_shield_integrity == 0
+                    {
+                        sound_effect_writer.send(SoundEffectEvents::ErrorButton);
+                        continue;
+                    }
                     if player_stats.check_if_enough_energy(player_stats.shield_energy_cost) {
                         player_stats.is_regaining_energy = false;
                         shield_resource.is_active = true;
                         shield_resource.time_till_next_cost = 0.0;
+                        shield_resource.blocked_enemies.clear();
+                        shield_resource.raised_at = time.seconds_since_startup();
+                        shield_resource.is_counter_pulse_ready = player_stats.is_counter_pulse_upgrade;
                         player_stats.shield_cost();
                         sound_effect_writer.send(SoundEffectEvents::ShieldOn(true));
                         shield(&mut shield_query, &mut commands);
                     }
-                } else {
+                } else if shield_resource.is_active {
                     player_stats.is_regaining_energy = true;
                     shield_resource.is_active = false;
                     sound_effect_writer.send(SoundEffectEvents::ShieldOn(false));
                     remove_shield(&mut shield_query, &mut commands);
                 }
             }
+            PlayerInputEvents::ToggleShieldMode => {
+                shield_resource.mode = match shield_resource.mode {
+                    ShieldMode::Arc => ShieldMode::Bubble,
+                    ShieldMode::Bubble => ShieldMode::Arc,
+                };
+                for mut path in shield_paths.iter_mut() {
+                    *path = shield_path(shield_resource.mode, player_stats.arc_shield_half_angle);
+                }
+            }
         }
     }
 }
@@ -151,7 +346,7 @@ pub(crate) fn shield(
     mut commands: &mut Commands,
 ) {
     for (entity, mut visibility) in shield_query.iter_mut() {
-        commands.entity(entity).insert(Collider::ball(60.));
+        commands.entity(entity).insert(Collider::ball(SHIELD_RADIUS));
         *visibility = Visibility { is_visible: true };
     }
 }
@@ -167,17 +362,137 @@ pub(crate) fn remove_shield(
 }
 
 pub(crate) fn handle_player_shield_collisions(
-    mut shield: Query<&CollidingEntities, With<ShieldComp>>,
-    mut enemy_entities: Query<&Enemy>,
+    time: Res<Time>,
+    sprites: Res<AssetHolder>,
+    mut shield_colliders: Query<&CollidingEntities, With<ShieldComp>>,
+    mut shield_query: Query<(Entity, &mut Visibility), With<ShieldComp>>,
+    mut enemy_entities: Query<(Entity, &mut EnemyHealth, &Transform), With<Enemy>>,
+    mut shield_resource: ResMut<ShieldRes>,
+    mut player_stats: ResMut<PlayerStats>,
     mut commands: Commands,
     mut sound_effect_writer: EventWriter<SoundEffectEvents>,
 ) {
-    if let Ok(shield) = shield.get_single_mut() {
+    if let Ok(shield) = shield_colliders.get_single_mut() {
+        // an enemy is only hit once per contact, it has to leave the shield to be hit again
+        shield_resource
+            .blocked_enemies
+            .retain(|enemy| shield.contains(*enemy));
         for collision in shield.iter() {
-            if let Ok(_enemy) = enemy_entities.get(collision) {
+            if shield_resource.blocked_enemies.contains(&collision) {
+                continue;
+            }
+            if let Ok((_, mut enemy_health, transform)) = enemy_entities.get_mut(collision) {
+                // the collider is always the full circle, the arc is checked by angle
+                if !shield_resource.covers(
+                    transform.translation.truncate(),
+                    player_stats.arc_shield_half_angle,
+                ) {
+                    continue;
+                }
                 sound_effect_writer.send(SoundEffectEvents::ShieldHit);
-                commands.entity(collision).insert(Destroyed);
+                shield_resource.blocked_enemies.push(collision);
+                damage_enemy(
+                    &mut commands,
+                    collision,
+                    &mut enemy_health,
+                    player_stats.shield_damage,
+                    KillSource::Shield,
+                );
+                if shield_resource.is_counter_pulse_ready
+                    && time.seconds_since_startup() - shield_resource.raised_at
+                        <= player_stats.counter_pulse_window as f64
+                {
+                    // a perfect block doesn't wear down the shield
+                    shield_resource.is_counter_pulse_ready = false;
+                    let center = shield_resource.center;
+                    counter_pulse(
+                        &sprites,
+                        &mut commands,
+                        center,
+                        collision,
+                        &mut shield_resource.blocked_enemies,
+                        &mut enemy_entities,
+                        &mut player_stats,
+                    );
+                    sound_effect_writer.send(SoundEffectEvents::MissileExplosion);
+                    continue;
+                }
+                if player_stats.shield_blocked() {
+                    // overloaded, the shield drops and stays down until the cooldown is over
+                    player_stats.is_regaining_energy = true;
+                    shield_resource.is_active = false;
+                    shield_resource.overload_time_left = player_stats.shield_overload_cooldown;
+                    sound_effect_writer.send(SoundEffectEvents::ShieldOn(false));
+                    remove_shield(&mut shield_query, &mut commands);
+                    break;
+                }
             }
         }
     }
 }
+
+/// The shockwave from a perfectly timed block. Damages and reveals every enemy in range and refunds some energy.
+/// blocked_enemy already took the shield hit so it is only revealed. Everything the pulse hits counts as blocked
+/// so the shield doesn't hit it again while it's still in contact
+fn counter_pulse(
+    sprites: &Res<AssetHolder>,
+    commands: &mut Commands,
+    center: Vec2,
+    blocked_enemy: Entity,
+    blocked_enemies: &mut Vec<Entity>,
+    enemy_entities: &mut Query<(Entity, &mut EnemyHealth, &Transform), With<Enemy>>,
+    player_stats: &mut ResMut<PlayerStats>,
+) {
+    let radius = player_stats.counter_pulse_radius;
+    for (enemy, mut enemy_health, transform) in enemy_entities.iter_mut() {
+        if transform.translation.truncate().distance(center) > radius {
+            continue;
+        }
+        commands.entity(enemy).insert(Scanned { origin: center });
+        if enemy == blocked_enemy {
+            continue;
+        }
+        if !blocked_enemies.contains(&enemy) {
+            blocked_enemies.push(enemy);
+        }
+        damage_enemy(
+            commands,
+            enemy,
+            &mut enemy_health,
+            player_stats.counter_pulse_damage,
+            KillSource::Shield,
+        );
+    }
+    player_stats.counter_pulse_refund();
+    // the pulse sprite is 32px across
+    spawn_weapon_effect(
+        commands,
+        sprites.player_missile_explosion_medium.clone(),
+        Color::WHITE,
+        Transform {
+            translation: center.extend(2.0),
+            scale: Vec3::new(radius * 2. / 32., radius * 2. / 32., 1.),
+            ..default()
+        },
+        0.5,
+    );
+}
+
+fn handle_restart_game_events(
+    mut shield_resource: ResMut<ShieldRes>,
+    mut shield_query: Query<(Entity, &mut Visibility), With<ShieldComp>>,
+    mut shield_paths: Query<&mut Path, With<ShieldComp>>,
+    player_stats: Res<PlayerStats>,
+    mut commands: Commands,
+) {
+    shield_resource.is_active = false;
+    shield_resource.blocked_enemies.clear();
+    shield_resource.mode = ShieldMode::Bubble;
+    shield_resource.overload_time_left = 0.0;
+    shield_resource.time_till_next_integrity = 0.0;
+    shield_resource.is_counter_pulse_ready = false;
+    remove_shield(&mut shield_query, &mut commands);
+    for mut path in shield_paths.iter_mut() {
+        *path = shield_path(ShieldMode::Bubble, player_stats.arc_shield_half_angle);
+    }
+}
@@ -51,6 +51,9 @@ pub(crate) enum SoundEffectEvents {
     ShieldOn(bool),
     ShieldHit,
 
+    //music
+    BossMusic(bool),
+
     //UI
     NormalButton,
     SmallUpgradeButton,
@@ -112,6 +115,7 @@ fn handle_sound_events(
     sounds: Res<SoundAssetHolder>,
     audio: Res<AudioChannel<Effects>>,
     shield_audio: Res<AudioChannel<ShieldAudio>>,
+    bg_audio: Res<AudioChannel<Background>>,
     game_settings: Res<GameSettings>,
 ) {
     for event in sound_event.iter() {
@@ -186,6 +190,28 @@ fn handle_sound_events(
                     .play(sounds.shield_hit.clone())
                     .with_volume(3.0 * game_settings.effects_sound_level.1);
             }
+
+            SoundEffectEvents::BossMusic(bool) => {
+                if game_settings.is_bg_sound_on {
+                    bg_audio.stop();
+                    match *bool {
+                        // the boss cue is the main loop played faster and louder
+                        true => {
+                            bg_audio
+                                .play(sounds.music.clone())
+                                .with_volume(0.15)
+                                .with_playback_rate(1.25)
+                                .looped();
+                        }
+                        false => {
+                            bg_audio
+                                .play(sounds.music.clone())
+                                .with_volume(0.10)
+                                .looped();
+                        }
+                    }
+                }
+            }
         }
     }
 }
@@ -3,6 +3,10 @@ use crate::*;
 use bevy::app::AppExit;
 
 use crate::egui::style::Margin;
+use crate::player::player_missiles::cluster_patterns::ClusterTiers;
+use crate::player::player_missiles::weapons::WeaponLoadout;
+use crate::player::scanner::sensor_buoys::SensorBuoy;
+use crate::player::shield::shield_core::{ShieldMode, ShieldRes};
 use crate::sound::SoundEffectEvents;
 use bevy::prelude::*;
 use bevy_egui::egui::*;
@@ -131,6 +135,7 @@ fn outside_backgrounds(
     mut egui_context: ResMut<EguiContext>,
     windows: Res<Windows>,
     sprites: Res<AssetHolder>,
+    play_field: Res<PlayField>,
 ) {
     let wnd = windows.get_primary().unwrap();
 
@@ -150,7 +155,7 @@ fn outside_backgrounds(
         .show(egui_context.ctx_mut(), |ui| {
             let sizer = ui.add_sized(
                 egui::Vec2 {
-                    x: (wnd.width() as f32 / 2.) - (wnd.height() as f32 / 2.),
+                    x: play_field.side_panel_width(),
                     y: 2000.,
                 },
                 Label::new(""),
@@ -158,7 +163,7 @@ fn outside_backgrounds(
             ui.image(
                 bg,
                 [
-                    (wnd.width() as f32 / 2.) - (wnd.height() as f32 / 2.),
+                    play_field.side_panel_width(),
                     2000.,
                 ],
             );
@@ -171,7 +176,7 @@ fn outside_backgrounds(
         .show(egui_context.ctx_mut(), |ui| {
             let sizer = ui.add_sized(
                 egui::Vec2 {
-                    x: (wnd.width() as f32 / 2.) - (wnd.height() as f32 / 2.),
+                    x: play_field.side_panel_width(),
                     y: 2000.,
                 },
                 Label::new(""),
@@ -179,7 +184,7 @@ fn outside_backgrounds(
             ui.image(
                 bg,
                 [
-                    (wnd.width() as f32 / 2.) - (wnd.height() as f32 / 2.),
+                    play_field.side_panel_width(),
                     2000.,
                 ],
             );
@@ -192,11 +197,11 @@ fn outside_backgrounds(
             vec2(0., -((wnd.height() as f32 / 2.) - (192. / 2.))),
         )
         .fixed_size(egui::Vec2 {
-            x: (wnd.width() as f32 / 2.) - (wnd.height() as f32 / 2.),
+            x: play_field.side_panel_width(),
             y: 500.,
         })
         .min_height(2000.)
-        .min_width((wnd.width() as f32 / 2.) - (wnd.height() as f32 / 2.))
+        .min_width(play_field.side_panel_width())
         .resizable(false)
         .collapsible(false)
         .title_bar(false)
@@ -216,6 +221,7 @@ fn main_menu_ui(
     mut sound_effect_writer: EventWriter<SoundEffectEvents>,
     mut sound_settings_writer: EventWriter<SoundSettingsEvents>,
     mut game_settings: ResMut<GameSettings>,
+    mut game_rng: ResMut<GameRng>,
 ) {
     let wnd = windows.get_primary().unwrap();
 
@@ -345,7 +351,8 @@ fn main_menu_ui(
                     }
                     2 => {
                         tutorial_text =
-                            "The invaders are invisible, scan for their location with [RMB]";
+                            "The invaders are invisible, scan for their location with [RMB]. \n\
+                            [MMB] or [Q] sends a cheaper, faster scan in a cone toward the mouse";
                     }
                     3 => {
                         tutorial_text = "Once you know where the invader is and what their speed is, launch a missile where you think they will be using [LMB].";
@@ -354,8 +361,9 @@ fn main_menu_ui(
                         tutorial_text = "Open the upgrade menu by pressing the pause button, pressing escape, or pressing tab";
                     }
                     5 => {
-                        tutorial_text = "Hold [space] to turn on the shield. \n\
-                        Energy generation is turned off when the shield is active";
+                        tutorial_text = "Hold [space] to turn on the shield. Press [F] to narrow it to a cheaper arc facing the mouse. \n\
+                        Energy generation is turned off when the shield is active. \n\
+                        Every enemy blocked wears down the shield, when it breaks it needs time to recover";
                     }
                     6 => {
                         tutorial_text = "That's it! Good luck!";
@@ -406,6 +414,68 @@ fn main_menu_ui(
                 });
                 ui.spacing_mut().item_spacing.y = 32.;
             });
+            // seed options so a run can be replayed or shared
+            ui.columns(2, |ui| {
+                ui[0].add_enabled(
+                    !game_settings.is_daily_seed,
+                    egui::TextEdit::singleline(&mut game_settings.seed_text)
+                        .hint_text("SEED")
+                        .desired_width(80.),
+                );
+                if game_settings.is_daily_seed {
+                    let menu_button = ui[1].add_sized(
+                        [80., 26.],
+                        egui::Button::new(
+                            RichText::new("DAILY")
+                                .text_style(small_button_font())
+                                .color(Color32::from_rgba_unmultiplied(0, 200, 0, 255)),
+                        ),
+                    );
+                    if menu_button.clicked() {
+                        sound_effect_writer.send(SoundEffectEvents::NormalButton);
+                        game_settings.toggle_daily_seed();
+                    };
+                } else {
+                    let menu_button = ui[1].add_sized(
+                        [80., 26.],
+                        egui::Button::new(RichText::new("DAILY").text_style(small_button_font())),
+                    );
+                    if menu_button.clicked() {
+                        sound_effect_writer.send(SoundEffectEvents::NormalButton);
+                        game_settings.toggle_daily_seed();
+                    };
+                }
+            });
+            // how many planets to defend and whether losing one ends the run
+            ui.columns(2, |ui| {
+                let menu_button = ui[0].add_sized(
+                    [80., 26.],
+                    egui::Button::new(
+                        RichText::new(game_settings.scenario.name()).text_style(small_button_font()),
+                    ),
+                );
+                if menu_button.clicked() {
+                    sound_effect_writer.send(SoundEffectEvents::NormalButton);
+                    game_settings.next_scenario();
+                };
+                let any_planet_text = if game_settings.is_lose_on_any_planet {
+                    RichText::new("ANY FALLS")
+                        .text_style(small_button_font())
+                        .color(Color32::from_rgba_unmultiplied(0, 200, 0, 255))
+                } else {
+                    RichText::new("ANY FALLS").text_style(small_button_font())
+                };
+                let menu_button = ui[1]
+                    .add_sized([80., 26.], egui::Button::new(any_planet_text))
+                    .on_hover_text(
+                        RichText::new("Lose as soon as any planet falls instead of once they all have")
+                            .text_style(small_button_font()),
+                    );
+                if menu_button.clicked() {
+                    sound_effect_writer.send(SoundEffectEvents::NormalButton);
+                    game_settings.toggle_lose_on_any_planet();
+                };
+            });
             // options below the main panel with system stuff
             ui.columns(2, |ui| {
                 let menu_button =
@@ -417,6 +487,8 @@ fn main_menu_ui(
                 let menu_button =
                     ui[1].add_sized([80., 26.], egui::Button::new(RichText::new("PLAY")));
                 if menu_button.clicked() {
+                    // the seed can be changed on the menu after the restart already seeded the run
+                    game_rng.reseed(game_settings.run_seed());
                     commands.insert_resource(NextState(GameState::Playing));
                     sound_effect_writer.send(SoundEffectEvents::NormalButton);
                 };
@@ -426,15 +498,16 @@ fn main_menu_ui(
 
 fn playing_ui(
     mut egui_context: ResMut<EguiContext>,
-    windows: Res<Windows>,
     sprites: Res<AssetHolder>,
     mut player_stats: ResMut<PlayerStats>,
     enemy_stats: Res<EnemyStats>,
+    play_field: Res<PlayField>,
+    weapon_loadout: Res<WeaponLoadout>,
+    shield_resource: Res<ShieldRes>,
+    buoys: Query<&SensorBuoy>,
     mut commands: Commands,
     mut sound_effect_writer: EventWriter<SoundEffectEvents>,
 ) {
-    let wnd = windows.get_primary().unwrap();
-
     let health = egui_context.add_image(sprites.health.clone_weak());
     let health_empty = egui_context.add_image(sprites.health_empty.clone_weak());
 
@@ -450,7 +523,7 @@ fn playing_ui(
         //.anchor(Align2::LEFT_CENTER, vec2(0., 0.))
         .anchor(Align2::LEFT_CENTER, vec2(0., 0.))
         .fixed_size(egui::Vec2 {
-            x: (wnd.width() as f32 / 2.) - (wnd.height() as f32 / 2.),
+            x: play_field.side_panel_width(),
             y: 20000.,
         })
         .resizable(false)
@@ -504,6 +577,36 @@ fn playing_ui(
                 );
             });
 
+            ui.columns(2, |ui| {
+                let eta_text = if player_stats.is_missile_eta_display {
+                    RichText::new("MISSILE ETA")
+                        .text_style(small_button_font())
+                        .color(Color32::from_rgba_unmultiplied(0, 200, 0, 255))
+                } else {
+                    RichText::new("MISSILE ETA").text_style(small_button_font())
+                };
+                let menu_button = ui[0].add_sized([80., 26.], egui::Button::new(eta_text));
+                if menu_button.clicked() {
+                    player_stats.toggle_missile_eta_display();
+                    sound_effect_writer.send(SoundEffectEvents::NormalButton);
+                };
+                // fuse delay for missiles fired with ctrl held
+                let fuse_info = player_stats.fuse_delay_info.clone();
+                ui[1]
+                    .add_sized(
+                        [80., 26.],
+                        egui::Slider::new(
+                            &mut player_stats.fuse_delay_info.0,
+                            fuse_info.1..=fuse_info.2,
+                        )
+                            .suffix("s"),
+                    )
+                    .on_hover_text(
+                        RichText::new("[Ctrl + LMB] missiles wait at their target this long before exploding")
+                            .text_style(small_button_font()),
+                    );
+            });
+
             ui.vertical_centered_justified(|ui| {
                 ui.spacing_mut().item_spacing.y = 8.;
                 ui.group(|ui| {
@@ -521,24 +624,97 @@ fn playing_ui(
                     ));
                 });
                 ui.group(|ui| {
-                    ui.label(RichText::new(format!(
-                        "HEALTH: {}/{}",
-                        player_stats.current_health, player_stats.max_health
-                    )));
-                    ui.horizontal_wrapped(|ui| {
-                        for i in 0..player_stats.max_health {
-                            if i > 26 {
-                                ui.label(RichText::new(format!("    ...")));
-                                break;
-                            } else {
-                                if i < player_stats.current_health {
-                                    ui.image(health, [(16 * 3) as f32, (16 * 3) as f32]);
+                    ui.label("SHIELD");
+                    if shield_resource.overload_time_left() > 0. {
+                        ui.add(
+                            ProgressBar::new(
+                                1. - shield_resource.overload_time_left()
+                                    / player_stats.shield_overload_cooldown,
+                            )
+                                .text(&format!(
+                                    "   OVERLOADED {:.1}s",
+                                    shield_resource.overload_time_left()
+                                )),
+                        );
+                    } else {
+                        ui.add(
+                            ProgressBar::new(
+                                player_stats.current_shield_integrity as f32
+                                    / player_stats.shield_max_integrity as f32,
+                            )
+                                .text(&format!(
+                                    "   INTEGRITY: {}/{}",
+                                    player_stats.current_shield_integrity,
+                                    player_stats.shield_max_integrity
+                                )),
+                        );
+                    }
+                });
+                ui.group(|ui| {
+                    let planet_count = player_stats.planet_health.len();
+                    for (index, current_health) in player_stats.planet_health.iter().enumerate() {
+                        if planet_count > 1 {
+                            ui.label(RichText::new(format!(
+                                "PLANET {}: {}/{}",
+                                index + 1,
+                                current_health,
+                                player_stats.max_health
+                            )));
+                        } else {
+                            ui.label(RichText::new(format!(
+                                "HEALTH: {}/{}",
+                                current_health, player_stats.max_health
+                            )));
+                        }
+                        ui.horizontal_wrapped(|ui| {
+                            for i in 0..player_stats.max_health {
+                                if i > 26 {
+                                    ui.label(RichText::new(format!("    ...")));
+                                    break;
                                 } else {
-                                    ui.image(health_empty, [(16 * 3) as f32, (16 * 3) as f32]);
+                                    if i < *current_health {
+                                        ui.image(health, [(16 * 3) as f32, (16 * 3) as f32]);
+                                    } else {
+                                        ui.image(health_empty, [(16 * 3) as f32, (16 * 3) as f32]);
+                                    }
                                 }
                             }
+                        });
+                    }
+                });
+                ui.group(|ui| {
+                    ui.label("WEAPONS");
+                    for (index, weapon_type) in weapon_loadout.weapons.iter().enumerate() {
+                        let mut text = format!(
+                            "[{}] {} ({})",
+                            index + 1,
+                            weapon_type.name(),
+                            weapon_type.energy_cost(&player_stats)
+                        );
+                        let cooldown = weapon_loadout.cooldowns[index];
+                        if cooldown > 0. {
+                            text.push_str(&format!(" {:.1}s", cooldown));
                         }
-                    });
+                        let mut text = RichText::new(text).text_style(small_button_font());
+                        if index == weapon_loadout.selected {
+                            text = text.color(Color32::from_rgba_unmultiplied(0, 200, 0, 255));
+                        }
+                        ui.label(text);
+                    }
+                });
+                ui.group(|ui| {
+                    ui.label(&format!(
+                        "BUOYS [B]: {}/{}",
+                        buoys.iter().count(),
+                        player_stats.buoy_limit
+                    ));
+                    ui.label(
+                        RichText::new(format!(
+                            "-1 Energy every {}s per buoy",
+                            player_stats.buoy_drain_rate
+                        ))
+                            .text_style(small_button_font()),
+                    );
                 });
                 ui.group(|ui| {
                     ui.label("STATS");
@@ -567,10 +743,17 @@ fn playing_ui(
                         ui.label(
                             RichText::new(format!(
                                 "Shield Time Per Cost: {}",
-                                player_stats.shield_cost_rate
+                                player_stats.shield_cost_rate_for(shield_resource.mode() == ShieldMode::Arc)
                             ))
                                 .text_style(small_button_font()),
                         );
+                        ui.label(
+                            RichText::new(match shield_resource.mode() {
+                                ShieldMode::Arc => "Shield [F]: ARC",
+                                ShieldMode::Bubble => "Shield [F]: BUBBLE",
+                            })
+                                .text_style(small_button_font()),
+                        );
                     });
                 });
             });
@@ -581,7 +764,7 @@ fn playing_ui(
         .frame(my_frame)
         .anchor(Align2::RIGHT_CENTER, vec2(0., 0.))
         .fixed_size(egui::Vec2 {
-            x: (wnd.width() as f32 / 2.) - (wnd.height() as f32 / 2.),
+            x: play_field.side_panel_width(),
             y: 500.,
         })
         .resizable(false)
@@ -612,6 +795,7 @@ fn pause_ui(
     mut sound_effect_writer: EventWriter<SoundEffectEvents>,
     mut sound_settings_writer: EventWriter<SoundSettingsEvents>,
     mut game_settings: ResMut<GameSettings>,
+    cluster_tiers: Res<ClusterTiers>,
 ) {
     let wnd = windows.get_primary().unwrap();
 
@@ -919,7 +1103,7 @@ fn pause_ui(
                                 let max_energy_button = button.response.interact(Sense::click());
                                 let max_energy_button = max_energy_button.on_hover_text(
                                     RichText::new(format!(
-                                        "Heals 1 Health | Cost: {}",
+                                        "Heals 1 Health on the most damaged planet | Cost: {}",
                                         player_stats.current_health_increase_cost
                                     ))
                                         .text_style(small_button_font()),
@@ -933,6 +1117,127 @@ fn pause_ui(
                                 }
                             });
 
+                            //TRAJECTORY ASSIST
+                            ui[1].vertical_centered(|ui| {
+                                ui.set_max_height(20.);
+                                ui.set_min_width(100.);
+
+                                let button = ui.group(|ui| {
+                                    if player_stats.check_trajectory_assist_maxed() {
+                                        ui.label(
+                                            RichText::new("Trajectory Assist")
+                                                .text_style(small_button_font()).strikethrough().color(Color32::from_rgba_unmultiplied(0, 200, 0, 255)),
+                                        );
+                                    } else {
+                                        ui.label(
+                                            RichText::new(format!(
+                                                "Trajectory Assist {}/{}",
+                                                player_stats.trajectory_assist_level.0,
+                                                player_stats.trajectory_assist_level.1
+                                            ))
+                                                .text_style(small_button_font()),
+                                        );
+                                    }
+                                });
+                                let hover_text = if player_stats.trajectory_assist_level.0 == 0 {
+                                    "Scanned enemies leave a line showing where they are heading"
+                                } else {
+                                    "Also marks where a missile fired now would meet the enemy"
+                                };
+                                let max_energy_button = button.response.interact(Sense::click());
+                                let max_energy_button = max_energy_button.on_hover_text(
+                                    RichText::new(format!(
+                                        "{} | Cost: {}",
+                                        hover_text, player_stats.trajectory_assist_upgrade_cost
+                                    ))
+                                        .text_style(small_button_font()),
+                                );
+                                if max_energy_button.clicked() {
+                                    if player_stats.upgrade_trajectory_assist() {
+                                        sound_effect_writer.send(SoundEffectEvents::SmallUpgradeButton);
+                                    } else {
+                                        sound_effect_writer.send(SoundEffectEvents::ErrorButton);
+                                    }
+                                }
+                            });
+
+                            //SATELLITES
+                            ui[1].vertical_centered(|ui| {
+                                ui.set_max_height(20.);
+                                ui.set_min_width(100.);
+
+                                let button = ui.group(|ui| {
+                                    if player_stats.check_satellite_count_maxed() {
+                                        ui.label(
+                                            RichText::new(format!(
+                                                "Satellites {}/{}",
+                                                player_stats.satellite_count.0,
+                                                player_stats.satellite_count.1
+                                            ))
+                                                .text_style(small_button_font()).strikethrough().color(Color32::from_rgba_unmultiplied(0, 200, 0, 255)),
+                                        );
+                                    } else {
+                                        ui.label(
+                                            RichText::new(format!(
+                                                "Satellites {}/{}",
+                                                player_stats.satellite_count.0,
+                                                player_stats.satellite_count.1
+                                            ))
+                                                .text_style(small_button_font()),
+                                        );
+                                    }
+                                });
+                                let max_energy_button = button.response.interact(Sense::click());
+                                let max_energy_button = max_energy_button.on_hover_text(
+                                    RichText::new(format!(
+                                        "Launches a satellite that shoots at scanned enemies near its orbit. Enemies that hit it destroy it | Cost: {}",
+                                        player_stats.satellite_upgrade_cost
+                                    ))
+                                        .text_style(small_button_font()),
+                                );
+                                if max_energy_button.clicked() {
+                                    if player_stats.upgrade_satellite_count() {
+                                        sound_effect_writer.send(SoundEffectEvents::SmallUpgradeButton);
+                                    } else {
+                                        sound_effect_writer.send(SoundEffectEvents::ErrorButton);
+                                    }
+                                }
+                            });
+                            ui[1].vertical_centered(|ui| {
+                                ui.set_max_height(20.);
+                                ui.set_min_width(100.);
+
+                                let button = ui.group(|ui| {
+                                    if player_stats.check_satellite_fire_rate_maxed() {
+                                        ui.label(
+                                            RichText::new("Satellite Fire Rate")
+                                                .text_style(small_button_font()).strikethrough().color(Color32::from_rgba_unmultiplied(0, 200, 0, 255)),
+                                        );
+                                    } else {
+                                        ui.label(
+                                            RichText::new("Satellite Fire Rate")
+                                                .text_style(small_button_font()),
+                                        );
+                                    }
+                                });
+                                let max_energy_button = button.response.interact(Sense::click());
+                                let max_energy_button = max_energy_button.on_hover_text(
+                                    RichText::new(format!(
+                                        "Satellites fire {}s sooner | Cost: {}",
+                                        player_stats.satellite_fire_rate.2,
+                                        player_stats.satellite_fire_rate_upgrade_cost
+                                    ))
+                                        .text_style(small_button_font()),
+                                );
+                                if max_energy_button.clicked() {
+                                    if player_stats.upgrade_satellite_fire_rate() {
+                                        sound_effect_writer.send(SoundEffectEvents::SmallUpgradeButton);
+                                    } else {
+                                        sound_effect_writer.send(SoundEffectEvents::ErrorButton);
+                                    }
+                                }
+                            });
+
                             //SCANS / shield / missile
                             ui[2].vertical_centered(|ui| {
                                 ui.set_max_height(20.);
@@ -969,6 +1274,85 @@ fn pause_ui(
                                     }
                                 }
                             });
+
+                            //GHOST TRAIL
+                            ui[2].vertical_centered(|ui| {
+                                ui.set_max_height(20.);
+                                ui.set_min_width(100.);
+
+                                let button = ui.group(|ui| {
+                                    if player_stats.check_ghost_trail_maxed() {
+                                        ui.label(
+                                            RichText::new("Ghost Trail")
+                                                .text_style(small_button_font()).strikethrough().color(Color32::from_rgba_unmultiplied(0, 200, 0, 255)),
+                                        );
+                                    } else {
+                                        ui.label(
+                                            RichText::new(format!(
+                                                "Ghost Trail {}/{}",
+                                                player_stats.ghost_trail_length.0,
+                                                player_stats.ghost_trail_length.1
+                                            ))
+                                                .text_style(small_button_font()),
+                                        );
+                                    }
+                                });
+
+                                let max_energy_button = button.response.interact(Sense::click());
+                                let max_energy_button = max_energy_button.on_hover_text(
+                                    RichText::new(format!(
+                                        "Scanned enemies leave {} more fading ghosts at their past sightings | Cost: {}",
+                                        player_stats.ghost_trail_length.2,
+                                        player_stats.ghost_trail_upgrade_cost
+                                    ))
+                                        .text_style(small_button_font()),
+                                );
+                                if max_energy_button.clicked() {
+                                    if player_stats.upgrade_ghost_trail() {
+                                        sound_effect_writer.send(SoundEffectEvents::SmallUpgradeButton);
+                                    } else {
+                                        sound_effect_writer.send(SoundEffectEvents::ErrorButton);
+                                    }
+                                }
+                            });
+
+                            //SENSOR ACCURACY
+                            ui[2].vertical_centered(|ui| {
+                                ui.set_max_height(20.);
+                                ui.set_min_width(100.);
+
+                                let button = ui.group(|ui| {
+                                    if player_stats.check_sensor_accuracy_maxed() {
+                                        ui.label(
+                                            RichText::new("Sensor Accuracy")
+                                                .text_style(small_button_font()).strikethrough().color(Color32::from_rgba_unmultiplied(0, 200, 0, 255)),
+                                        );
+                                    } else {
+                                        ui.label(
+                                            RichText::new("Sensor Accuracy")
+                                                .text_style(small_button_font()),
+                                        );
+                                    }
+                                });
+
+                                let max_energy_button = button.response.interact(Sense::click());
+                                let max_energy_button = max_energy_button.on_hover_text(
+                                    RichText::new(format!(
+                                        "Ghosts are off by up to {}% of their distance from the scan. Shrinks the error by {}% | Cost: {}",
+                                        (player_stats.sensor_accuracy.0 * 100.).round(),
+                                        (player_stats.sensor_accuracy.2 * 100.).round(),
+                                        player_stats.sensor_accuracy_upgrade_cost
+                                    ))
+                                        .text_style(small_button_font()),
+                                );
+                                if max_energy_button.clicked() {
+                                    if player_stats.upgrade_sensor_accuracy() {
+                                        sound_effect_writer.send(SoundEffectEvents::SmallUpgradeButton);
+                                    } else {
+                                        sound_effect_writer.send(SoundEffectEvents::ErrorButton);
+                                    }
+                                }
+                            });
                             ui[2].vertical_centered(|ui| {
                                 ui.set_max_height(20.);
                                 ui.set_min_width(100.);
@@ -999,6 +1383,32 @@ fn pause_ui(
                                 ui.set_max_height(20.);
                                 ui.set_min_width(100.);
 
+                                let button = ui.group(|ui| {
+                                    ui.label(
+                                        RichText::new("Shield Integrity")
+                                            .text_style(small_button_font()),
+                                    );
+                                });
+                                let max_energy_button = button.response.interact(Sense::click());
+                                let max_energy_button = max_energy_button.on_hover_text(
+                                    RichText::new(format!(
+                                        "The shield can block {} more enemy before overloading | Cost: {}",
+                                        1, player_stats.shield_integrity_upgrade_cost
+                                    ))
+                                        .text_style(small_button_font()),
+                                );
+                                if max_energy_button.clicked() {
+                                    if player_stats.upgrade_shield_integrity() {
+                                        sound_effect_writer.send(SoundEffectEvents::SmallUpgradeButton);
+                                    } else {
+                                        sound_effect_writer.send(SoundEffectEvents::ErrorButton);
+                                    }
+                                }
+                            });
+                            ui[2].vertical_centered(|ui| {
+                                ui.set_max_height(20.);
+                                ui.set_min_width(100.);
+
                                 let button = ui.group(|ui| {
                                     if player_stats.check_missile_speed_maxed() {
                                         ui.label(
@@ -1045,28 +1455,85 @@ fn pause_ui(
                                 ui.set_max_height(50.);
 
                                 //CLUSTER MISSILE
+                                let owned_tier = cluster_tiers.get(player_stats.cluster_missile_level.0);
                                 let button = ui.group(|ui| {
                                     ui.set_min_width(100.);
-                                    if player_stats.is_cluster_missile_upgrade {
+                                    let cluster_text = match &owned_tier {
+                                        Some(cluster_tier) => format!(
+                                            "Cluster Missile: {} {}/{}",
+                                            cluster_tier.name,
+                                            player_stats.cluster_missile_level.0,
+                                            player_stats.cluster_missile_level.1
+                                        ),
+                                        None => "Cluster Missile".to_string(),
+                                    };
+                                    if player_stats.check_cluster_missile_maxed() {
                                         ui.label(
-                                            RichText::new("Cluster Missile").text_style(small_button_font()).strikethrough().color(Color32::from_rgba_unmultiplied(0, 200, 0, 255)),
+                                            RichText::new(cluster_text).text_style(small_button_font()).strikethrough().color(Color32::from_rgba_unmultiplied(0, 200, 0, 255)),
+                                        );
+                                    } else if owned_tier.is_some() {
+                                        ui.label(
+                                            RichText::new(cluster_text).text_style(small_button_font()).color(Color32::from_rgba_unmultiplied(0, 200, 0, 255)),
+                                        );
+                                    } else {
+                                        ui.label(
+                                            RichText::new(cluster_text).text_style(small_button_font()).color(Color32::from_rgba_unmultiplied(200, 0, 0, 255)),
+                                        );
+                                    }
+                                });
+                                let max_energy_button = button.response.interact(Sense::click());
+                                let hover_text = if player_stats.check_cluster_missile_maxed() {
+                                    "Cluster missile is fully upgraded".to_string()
+                                } else {
+                                    let next_tier = cluster_tiers
+                                        .get(player_stats.cluster_missile_level.0 + 1)
+                                        .unwrap_or_default();
+                                    format!(
+                                        "Next tier: {} - fires {} extra missiles in a {} pattern around the target point | Cost: {}",
+                                        next_tier.name,
+                                        next_tier.count,
+                                        format!("{:?}", next_tier.pattern).to_lowercase(),
+                                        player_stats.cluster_missile_upgrade_cost
+                                    )
+                                };
+                                let max_energy_button = max_energy_button.on_hover_text(
+                                    RichText::new(hover_text).text_style(small_button_font()),
+                                );
+                                if max_energy_button.clicked() {
+                                    if player_stats.upgrade_cluster_missile() {
+                                        sound_effect_writer.send(SoundEffectEvents::UpgradeButton);
+                                    } else {
+                                        sound_effect_writer.send(SoundEffectEvents::ErrorButton);
+                                    }
+                                }
+                            });
+                            ui[0].vertical_centered(|ui| {
+                                ui.set_max_height(50.);
+
+                                //HOMING MISSILE
+                                let button = ui.group(|ui| {
+                                    ui.set_min_width(100.);
+                                    if player_stats.is_homing_missile_upgrade {
+                                        ui.label(
+                                            RichText::new("Homing Missile").text_style(small_button_font()).strikethrough().color(Color32::from_rgba_unmultiplied(0, 200, 0, 255)),
                                         );
                                     } else {
                                         ui.label(
-                                            RichText::new("Cluster Missile").text_style(small_button_font()).color(Color32::from_rgba_unmultiplied(200, 0, 0, 255)),
+                                            RichText::new("Homing Missile").text_style(small_button_font()).color(Color32::from_rgba_unmultiplied(200, 0, 0, 255)),
                                         );
                                     }
                                 });
                                 let max_energy_button = button.response.interact(Sense::click());
                                 let max_energy_button = max_energy_button.on_hover_text(
                                     RichText::new(format!(
-                                        "Fires 4 missiles in an aoe around the target point | Cost: {}",
-                                        player_stats.cluster_missile_upgrade_cost
+                                        "[Shift + LMB] fires a missile that locks onto the nearest visible enemy. Costs {} energy | Cost: {}",
+                                        player_stats.homing_missile_energy_cost,
+                                        player_stats.homing_missile_upgrade_cost
                                     ))
                                         .text_style(small_button_font()),
                                 );
                                 if max_energy_button.clicked() {
-                                    if player_stats.upgrade_cluster_missile() {
+                                    if player_stats.upgrade_homing_missile() {
                                         sound_effect_writer.send(SoundEffectEvents::UpgradeButton);
                                     } else {
                                         sound_effect_writer.send(SoundEffectEvents::ErrorButton);
@@ -1104,6 +1571,36 @@ fn pause_ui(
                                         sound_effect_writer.send(SoundEffectEvents::ErrorButton);
                                     }
                                 }
+
+                                //CHAIN REACTION
+                                let button = ui.group(|ui| {
+                                    ui.set_min_width(100.);
+                                    if player_stats.is_chain_reaction_upgrade {
+                                        ui.label(
+                                            RichText::new("Chain Reaction").text_style(small_button_font()).strikethrough().color(Color32::from_rgba_unmultiplied(0, 200, 0, 255)),
+                                        );
+                                    } else {
+                                        ui.label(
+                                            RichText::new("Chain Reaction").text_style(small_button_font()).color(Color32::from_rgba_unmultiplied(200, 0, 0, 255)),
+                                        );
+                                    }
+                                });
+                                let max_energy_button = button.response.interact(Sense::click());
+                                let max_energy_button = max_energy_button.on_hover_text(
+                                    RichText::new(format!(
+                                        "Destroyed enemies explode and can destroy enemies near them, up to {} blasts in a row. Each link earns more score | Cost: {}",
+                                        player_stats.chain_reaction_max_depth,
+                                        player_stats.chain_reaction_upgrade_cost
+                                    ))
+                                        .text_style(small_button_font()),
+                                );
+                                if max_energy_button.clicked() {
+                                    if player_stats.upgrade_chain_reaction() {
+                                        sound_effect_writer.send(SoundEffectEvents::UpgradeButton);
+                                    } else {
+                                        sound_effect_writer.send(SoundEffectEvents::ErrorButton);
+                                    }
+                                }
                             });
                             ui[2].vertical_centered(|ui| {
                                 ui.set_max_height(50.);
@@ -1137,27 +1634,74 @@ fn pause_ui(
                                     }
                                 }
                             });
+                            ui[2].vertical_centered(|ui| {
+                                ui.set_max_height(50.);
+
+                                //COUNTER PULSE
+                                let button = ui.group(|ui| {
+                                    ui.set_min_width(100.);
+                                    if player_stats.is_counter_pulse_upgrade {
+                                        ui.label(
+                                            RichText::new("Counter Pulse").text_style(small_button_font()).strikethrough().color(Color32::from_rgba_unmultiplied(0, 200, 0, 255)),
+                                        );
+                                    } else {
+                                        ui.label(
+                                            RichText::new("Counter Pulse").text_style(small_button_font()).color(Color32::from_rgba_unmultiplied(200, 0, 0, 255)),
+                                        );
+                                    }
+                                });
+                                let max_energy_button = button.response.interact(Sense::click());
+                                let max_energy_button = max_energy_button.on_hover_text(
+                                    RichText::new(format!(
+                                        "Blocking an enemy within {}ms of raising the shield releases a shockwave that damages and reveals nearby enemies and refunds {} energy | Cost: {}",
+                                        (player_stats.counter_pulse_window * 1000.) as u32,
+                                        player_stats.counter_pulse_energy_refund,
+                                        player_stats.counter_pulse_upgrade_cost
+                                    ))
+                                        .text_style(small_button_font()),
+                                );
+                                if max_energy_button.clicked() {
+                                    if player_stats.upgrade_counter_pulse() {
+                                        sound_effect_writer.send(SoundEffectEvents::UpgradeButton);
+                                    } else {
+                                        sound_effect_writer.send(SoundEffectEvents::ErrorButton);
+                                    }
+                                }
+                            });
                             ui[3].vertical_centered(|ui| {
                                 ui.set_max_height(50.);
 
                                 //LARGER MISSILES
                                 let button = ui.group(|ui| {
                                     ui.set_min_width(100.);
-                                    if player_stats.is_larger_missiles_upgrade {
+                                    if player_stats.check_larger_missiles_maxed() {
                                         ui.label(
                                             RichText::new("Larger Missiles").text_style(small_button_font()).strikethrough().color(Color32::from_rgba_unmultiplied(0, 200, 0, 255)),
                                         );
+                                    } else if player_stats.larger_missiles_level.0 > 0 {
+                                        ui.label(
+                                            RichText::new(format!(
+                                                "Larger Missiles {}/{}",
+                                                player_stats.larger_missiles_level.0,
+                                                player_stats.larger_missiles_level.1
+                                            )).text_style(small_button_font()).color(Color32::from_rgba_unmultiplied(0, 200, 0, 255)),
+                                        );
                                     } else {
                                         ui.label(
                                             RichText::new("Larger Missiles").text_style(small_button_font()).color(Color32::from_rgba_unmultiplied(200, 0, 0, 255)),
                                         );
                                     }
                                 });
+                                let hover_text = if player_stats.larger_missiles_level.0 == 0 {
+                                    "Larger explosion radius for all missiles"
+                                } else {
+                                    "Large explosions with an even bigger radius"
+                                };
                                 let max_energy_button = button.response.interact(Sense::click());
                                 let max_energy_button = max_energy_button.on_hover_text(
                                     RichText::new(format!(
-                                        "Larger explosion radius for all missiles | Cost: {}",
-                                        player_stats.larger_missiles_upgrade_cost
+                                        "{} | Cost: {}",
+                                        hover_text, player_stats.larger_missiles_upgrade_cost
                                     ))
                                         .text_style(small_button_font()),
                                 );
@@ -1219,6 +1763,7 @@ fn lose_ui(
     mut commands: Commands,
     player_stats: Res<PlayerStats>,
     enemy_stats: Res<EnemyStats>,
+    game_rng: Res<GameRng>,
     mut sound_effect_writer: EventWriter<SoundEffectEvents>,
 ) {
     let wnd = windows.get_primary().unwrap();
@@ -1245,6 +1790,7 @@ fn lose_ui(
                             "You fought {} invaders!",
                             enemy_stats.all_time_enemy_count
                         ));
+                        ui.label(&format!("SEED: {}", game_rng.seed));
                     });
                 });
                 ui.spacing_mut().item_spacing.y = 32.;